@@ -0,0 +1,85 @@
+use age::secrecy::SecretString;
+use std::io::{BufRead, Read, Write};
+use std::path::Path;
+use std::str::FromStr;
+
+// age 파일 시작 부분 (바이너리 / ASCII armor)
+const AGE_MAGIC: &[u8] = b"age-encryption.org/v1\n";
+const ARMOR_BEGIN: &[u8] = b"-----BEGIN AGE ENCRYPTED FILE-----";
+
+/******************* 표준 age 형식 (https://age-encryption.org/v1) ******************/
+// age 명령줄 도구와 주고받을 수 있도록 파일 이름 등 메타데이터 없이 내용만 암호화함
+pub fn is_age(data: &[u8]) -> bool {
+    data.starts_with(AGE_MAGIC) || data.trim_ascii_start().starts_with(ARMOR_BEGIN)
+}
+
+// age 는 X25519 수신자와 비밀번호(scrypt)를 함께 쓸 수 없으므로 둘 중 하나만 받음
+pub fn encrypt(content: &[u8], recipients: &[String], passphrase: Option<&str>) -> Result<Vec<u8>, String> {
+    let encryptor = match (recipients.is_empty(), passphrase) {
+        (true, Some(passphrase)) => age::Encryptor::with_user_passphrase(SecretString::from(passphrase.to_string())),
+        (false, None) => {
+            let parsed = recipients
+                .iter()
+                .map(|r| age::x25519::Recipient::from_str(r.trim()).map_err(|_| "Invalid public key".to_string()))
+                .collect::<Result<Vec<_>, _>>()?;
+            age::Encryptor::with_recipients(parsed.iter().map(|r| r as &dyn age::Recipient)).map_err(|e| e.to_string())?
+        }
+        (true, None) => return Err("age output needs recipients or a passphrase".into()),
+        (false, Some(_)) => return Err("age output cannot combine recipients and a passphrase".into()),
+    };
+
+    let mut output = Vec::with_capacity(content.len() + 256);
+    let mut writer = encryptor.wrap_output(&mut output).map_err(|e| e.to_string())?;
+    writer.write_all(content).map_err(|e| e.to_string())?;
+    writer.finish().map_err(|e| e.to_string())?;
+    Ok(output)
+}
+
+// 비밀번호로 암호화된 파일인지 (복호화 전에 비밀번호를 물어봐야 하는지)
+pub fn needs_passphrase(input: impl BufRead) -> bool {
+    let reader = age::armor::ArmoredReader::new(input);
+    age::Decryptor::new(reader).map(|d| d.is_scrypt()).unwrap_or(false)
+}
+
+// secret_keys 는 이 vault 의 X25519 비밀키 (AGE-SECRET-KEY-1...)
+pub fn decrypt(data: &[u8], secret_keys: &[&str], passphrase: Option<&str>) -> Result<Vec<u8>, String> {
+    let reader = age::armor::ArmoredReader::new(data);
+    let decryptor = age::Decryptor::new(reader).map_err(|e| format!("Invalid file: {}", e))?;
+
+    let mut identities: Vec<Box<dyn age::Identity>> = Vec::new();
+    if decryptor.is_scrypt() {
+        let passphrase = passphrase.ok_or("Decryption failed: this file is protected with a passphrase")?;
+        identities.push(Box::new(age::scrypt::Identity::new(SecretString::from(passphrase.to_string()))));
+    } else {
+        for key in secret_keys {
+            if let Ok(identity) = age::x25519::Identity::from_str(key) {
+                identities.push(Box::new(identity));
+            }
+        }
+    }
+
+    let mut reader = decryptor
+        .decrypt(identities.iter().map(|i| i.as_ref()))
+        .map_err(|e| match e {
+            age::DecryptError::NoMatchingKeys => {
+                "Decryption failed: no identity in this vault is a recipient of this file".to_string()
+            }
+            age::DecryptError::DecryptionFailed => "Decryption failed: wrong passphrase or damaged file".to_string(),
+            other => format!("Decryption failed: {}", other),
+        })?;
+    let mut content = Vec::new();
+    reader.read_to_end(&mut content).map_err(|e| format!("Decryption failed: {}", e))?;
+    Ok(content)
+}
+
+// report.txt → report.txt.age
+pub fn encrypted_name(source_path: &Path) -> String {
+    format!("{}.age", source_path.file_name().unwrap_or_default().to_string_lossy())
+}
+
+// report.txt.age → report.txt (확장자가 .age 가 아니면 이름을 그대로 씀)
+pub fn decrypted_name(source_path: &Path) -> String {
+    let is_age = source_path.extension().is_some_and(|ext| ext.eq_ignore_ascii_case("age"));
+    let name = if is_age { source_path.file_stem() } else { source_path.file_name() };
+    name.unwrap_or_default().to_string_lossy().to_string()
+}
@@ -0,0 +1,479 @@
+use aes_gcm::aead::{Aead, AeadCore, OsRng, Payload};
+use aes_gcm::{Aes256Gcm, Key, KeyInit, Nonce};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::fs::{self, OpenOptions};
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::sync::Mutex;
+
+use crate::progress;
+use crate::report::{self, JobStatus};
+use crate::subkey::{self, Purpose};
+use crate::vault_file;
+
+const HEAD_AAD: &[u8] = b"securevault audit head";
+const NONCE_LEN: usize = 12;
+
+// 기록하는 보안 관련 이벤트
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE", rename_all_fields = "camelCase", tag = "type")]
+pub enum AuditEvent {
+    VaultCreated,
+    UnlockSucceeded,
+    // 잠금 해제에 실패한 시점에는 키가 없으므로, 다음에 잠금 해제에 성공했을 때 기록함
+    UnlockFailed {
+        attempted_at: String,
+    },
+    PasswordChanged,
+    SettingsChanged,
+    HeaderExported {
+        rewrapped: bool, // 별도의 내보내기 비밀번호로 다시 암호화했는지
+    },
+    HeaderImported,
+    IdentityCreated {
+        public_key: String,
+    },
+    IdentityDeleted {
+        public_key: String,
+    },
+    ContactAdded {
+        name: String,
+        public_key: String,
+    },
+    ContactRemoved {
+        name: String,
+        public_key: String,
+    },
+    // 새 마스터 키로 교체 (이전 키는 파일을 모두 옮길 때까지 복호화에만 사용)
+    // 로그를 새 키로 옮기면서 검증에 실패한 부분을 버렸다면 log_problem 에 그 이유를 남김
+    KeyRotated {
+        key_id: String,
+        previous_key_id: String,
+        log_problem: Option<String>,
+    },
+    // 선택한 폴더의 파일을 모두 새 키로 옮긴 뒤 이전 키를 폐기
+    KeysRetired {
+        key_ids: Vec<String>,
+    },
+    // 암호화/복호화/보안 삭제 작업 하나의 결과
+    Job {
+        job_id: String,
+        operation: String,
+        status: JobStatus,
+        total: usize,
+        succeeded: usize,
+        failed: usize,
+        not_started: usize,
+    },
+    // 기록 도중 앱이 종료되어 끝부분에 남은 불완전한 항목을 잘라낸 경우
+    LogRepaired {
+        discarded_bytes: u64,
+    },
+    // 잠금 해제하면서 로그를 열 때 마지막 기록과 맞지 않았던 경우 (잠겨 있는 동안 잘리거나 지워짐)
+    // 이어 쓰면 마지막 기록이 새로 저장되므로, 발견한 문제를 먼저 로그에 남김
+    LogTampered {
+        problem: String,
+    },
+}
+
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AuditEntry {
+    pub seq: u64,
+    pub time: String,
+    pub prev_hash: String, // 이전 항목까지의 해시 체인 값 (16진수)
+    pub event: AuditEvent,
+}
+
+// 로그를 읽은 결과. problem 이 있으면 그 위치부터 수정/삭제된 것으로 봄
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct AuditLogView {
+    pub entries: Vec<AuditEntry>,
+    pub verified: bool,
+    pub problem: Option<String>,
+}
+
+// 마지막 항목 번호와 해시. 로그 끝이 잘렸는지 확인하는 데 사용
+#[derive(Serialize, Deserialize)]
+struct AuditHead {
+    seq: u64,
+    hash: String,
+}
+
+struct AuditSession {
+    log_path: PathBuf,
+    head_path: PathBuf,
+    key: Key<Aes256Gcm>,
+    next_seq: u64,
+    last_hash: [u8; 32],
+}
+
+/******************* 변경을 감지할 수 있는 암호화된 감사 로그 ******************/
+// vault 파일 옆의 <이름>.audit 파일에 항목을 하나씩 덧붙이기만 함
+// 항목마다 [길이(u32)] + [논스] + [암호문] 으로 저장하고, 암호문은 마스터 키에서 유도한 키로 암호화하며
+// 해시 체인(이전 해시 + 항목 바이트의 SHA-256)으로 이어서 중간 항목의 수정/삭제/순서 변경을 감지함
+// 마지막 항목 번호와 해시는 <이름>.audit-head 에 암호화해서 따로 저장하여 끝부분이 잘린 것도 감지함
+#[derive(Default)]
+pub struct AuditLog {
+    session: Mutex<Option<AuditSession>>,
+}
+
+impl AuditLog {
+    /******************* 새 vault 를 만들 때 이전 vault 의 로그를 지우고 시작 ******************/
+    // 이전 로그는 이전 마스터 키로 암호화되어 있어 더 이상 읽거나 이어 쓸 수 없음
+    pub fn create(&self, vault_path: &Path, vault_key: &Key<Aes256Gcm>) -> Result<(), String> {
+        let (log_path, head_path, pending_path) = side_paths(vault_path);
+        for path in [&log_path, &head_path, &pending_path] {
+            if path.exists() {
+                fs::remove_file(path).map_err(|e| e.to_string())?;
+            }
+        }
+        self.open_session(vault_path, vault_key, true)
+    }
+
+    /******************* 잠금 해제 후 로그를 이어 쓸 수 있도록 열기 ******************/
+    // 이미 있던 vault 의 로그이므로, 이어 쓰기 전에 마지막 기록(<이름>.audit-head)과 맞는지 먼저 확인함
+    // 로그나 마지막 기록이 없는 것도 지워진 것으로 봄 (새 로그는 create 로만 시작)
+    // 잠겨 있는 동안 쌓인 잠금 해제 실패 기록도 이때 옮겨 적음
+    pub fn open(&self, vault_path: &Path, vault_key: &Key<Aes256Gcm>) -> Result<(), String> {
+        self.open_session(vault_path, vault_key, false)
+    }
+
+    fn open_session(&self, vault_path: &Path, vault_key: &Key<Aes256Gcm>, is_new: bool) -> Result<(), String> {
+        let (log_path, head_path, pending_path) = side_paths(vault_path);
+        let key = subkey::derive(vault_key, Purpose::AuditLog);
+
+        let data = if log_path.exists() { fs::read(&log_path).map_err(|e| e.to_string())? } else { Vec::new() };
+        let (records, complete_len) = split_records(&data);
+        let mut last_hash = [0u8; 32];
+        let mut previous_hash = last_hash;
+        for record in &records {
+            previous_hash = last_hash;
+            last_hash = chain_hash(&last_hash, record);
+        }
+        let next_seq = records.len() as u64;
+
+        // 항목을 쓰고 마지막 기록을 쓰기 전에 중단된 경우는 마지막 기록이 한 항목 앞을 가리키므로 문제로 보지 않음
+        let cipher = Aes256Gcm::new(&key);
+        let tampered = if is_new {
+            None
+        } else if !log_path.exists() {
+            Some("The audit log file is missing.".to_string())
+        } else {
+            check_head(&cipher, &head_path, next_seq, &last_hash).filter(|_| {
+                next_seq == 0 || check_head(&cipher, &head_path, next_seq - 1, &previous_hash).is_some()
+            })
+        };
+
+        let mut repaired = None;
+        if complete_len < data.len() {
+            let file = OpenOptions::new().write(true).open(&log_path).map_err(|e| e.to_string())?;
+            file.set_len(complete_len as u64).map_err(|e| e.to_string())?;
+            repaired = Some((data.len() - complete_len) as u64);
+        }
+
+        *self.session.lock().unwrap() = Some(AuditSession { log_path, head_path, key, next_seq, last_hash });
+
+        if let Some(problem) = tampered {
+            self.record(AuditEvent::LogTampered { problem })?;
+        }
+        if let Some(discarded_bytes) = repaired {
+            self.record(AuditEvent::LogRepaired { discarded_bytes })?;
+        }
+        if let Ok(pending) = fs::read_to_string(&pending_path) {
+            for attempted_at in pending.lines().filter(|line| !line.is_empty()) {
+                self.record(AuditEvent::UnlockFailed { attempted_at: attempted_at.to_string() })?;
+            }
+            let _ = fs::remove_file(&pending_path);
+        }
+        Ok(())
+    }
+
+    // 잠그거나 다른 vault 로 바꿀 때 키를 메모리에서 지움
+    pub fn close(&self) {
+        *self.session.lock().unwrap() = None;
+    }
+
+    /******************* 항목 하나를 로그 끝에 덧붙임 ******************/
+    // 잠겨 있으면 기록하지 않음. 쓰지 못하면 오류를 반환해서 호출한 쪽이 알리도록 함 (작업은 보고서에, 명령은 반환값으로)
+    pub fn record(&self, event: AuditEvent) -> Result<(), String> {
+        let mut session = self.session.lock().unwrap();
+        let Some(session) = session.as_mut() else { return Ok(()) };
+        session.append(event).map_err(|e| format!("Failed to write audit log: {}", e))
+    }
+
+    /******************* 로그 전체를 복호화하고 해시 체인을 검증 ******************/
+    pub fn read(&self) -> Result<AuditLogView, String> {
+        let session = self.session.lock().unwrap();
+        let session = session.as_ref().ok_or("Vault is locked")?;
+
+        let data = if session.log_path.exists() {
+            fs::read(&session.log_path).map_err(|e| e.to_string())?
+        } else {
+            Vec::new()
+        };
+        let (records, complete_len) = split_records(&data);
+        let cipher = Aes256Gcm::new(&session.key);
+
+        let mut entries = Vec::new();
+        let mut problem = None;
+        let mut hash = [0u8; 32];
+        for (index, record) in records.iter().enumerate() {
+            let seq = index as u64;
+            let entry = decrypt(&cipher, record, &seq.to_be_bytes())
+                .and_then(|plaintext| serde_json::from_slice::<AuditEntry>(&plaintext).ok());
+            let Some(entry) = entry else {
+                problem = Some(format!("Entry {} could not be decrypted. It was modified or replaced.", seq));
+                break;
+            };
+            if entry.seq != seq || entry.prev_hash != to_hex(&hash) {
+                problem = Some(format!("Entry {} does not follow the previous entry. Entries were removed or reordered.", seq));
+                break;
+            }
+            hash = chain_hash(&hash, record);
+            entries.push(entry);
+        }
+
+        if problem.is_none() && complete_len < data.len() {
+            problem = Some("The log ends in the middle of an entry.".to_string());
+        }
+        if problem.is_none() {
+            problem = check_head(&cipher, &session.head_path, entries.len() as u64, &hash);
+        }
+
+        Ok(AuditLogView { entries, verified: problem.is_none(), problem })
+    }
+
+    /******************* 마스터 키를 바꿀 때 로그 전체를 새 키로 다시 암호화 ******************/
+    // 이전 키로 로그를 열어 검증한 뒤, 항목 내용은 그대로 두고 새 키로 암호화하면서 해시 체인을 다시 계산함
+    // 검증에 실패한 지점부터는 옮기지 않으므로 그 이유를 반환해서 교체 기록에 남기게 함
+    // 이전 키로 열리지 않는 로그(이미 옮긴 로그 등)면 새 키로 다시 열고 Err
+    pub fn rekey(&self, vault_path: &Path, old_vault_key: &Key<Aes256Gcm>, new_vault_key: &Key<Aes256Gcm>) -> Result<Option<String>, String> {
+        self.open(vault_path, old_vault_key)?;
+        let view = self.read()?;
+        if view.entries.is_empty() && !view.verified {
+            self.open(vault_path, new_vault_key)?;
+            return Err("The audit log is not encrypted with the previous key".into());
+        }
+
+        let mut session = self.session.lock().unwrap();
+        let session = session.as_mut().ok_or("Vault is locked")?;
+        let key = subkey::derive(new_vault_key, Purpose::AuditLog);
+        let cipher = Aes256Gcm::new(&key);
+        let mut data = Vec::new();
+        let mut hash = [0u8; 32];
+        let count = view.entries.len() as u64;
+        for mut entry in view.entries {
+            entry.prev_hash = to_hex(&hash);
+            let plaintext = serde_json::to_vec(&entry).map_err(|e| e.to_string())?;
+            let record = encrypt(&cipher, &plaintext, &entry.seq.to_be_bytes())?;
+            data.extend_from_slice(&(record.len() as u32).to_be_bytes());
+            data.extend_from_slice(&record);
+            hash = chain_hash(&hash, &record);
+        }
+        progress::write_with_progress(&session.log_path, &data, &mut |_| {})?;
+        session.key = key;
+        session.next_seq = count;
+        session.last_hash = hash;
+        session.write_head(&cipher)?;
+        Ok(view.problem)
+    }
+}
+
+impl AuditSession {
+    fn append(&mut self, event: AuditEvent) -> Result<(), String> {
+        let entry = AuditEntry {
+            seq: self.next_seq,
+            time: report::now_utc(),
+            prev_hash: to_hex(&self.last_hash),
+            event,
+        };
+        let plaintext = serde_json::to_vec(&entry).map_err(|e| e.to_string())?;
+        let cipher = Aes256Gcm::new(&self.key);
+        let record = encrypt(&cipher, &plaintext, &entry.seq.to_be_bytes())?;
+
+        let mut file = OpenOptions::new().create(true).append(true).open(&self.log_path).map_err(|e| e.to_string())?;
+        let mut framed = (record.len() as u32).to_be_bytes().to_vec();
+        framed.extend_from_slice(&record);
+        file.write_all(&framed).map_err(|e| e.to_string())?;
+        file.sync_data().map_err(|e| e.to_string())?;
+
+        self.last_hash = chain_hash(&self.last_hash, &record);
+        self.next_seq += 1;
+        self.write_head(&cipher)
+    }
+
+    fn write_head(&self, cipher: &Aes256Gcm) -> Result<(), String> {
+        let head = AuditHead { seq: self.next_seq, hash: to_hex(&self.last_hash) };
+        let head_bytes = serde_json::to_vec(&head).map_err(|e| e.to_string())?;
+        let head_record = encrypt(cipher, &head_bytes, HEAD_AAD)?;
+        progress::write_with_progress(&self.head_path, &head_record, &mut |_| {})
+    }
+}
+
+/******************* 잠금 해제 실패를 다음 잠금 해제 때까지 보관 ******************/
+// 키가 없으므로 암호화하지 않고 시각만 남김
+pub fn record_unlock_failure(vault_path: &Path) -> Result<(), String> {
+    let (_, _, pending_path) = side_paths(vault_path);
+    OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(&pending_path)
+        .and_then(|mut file| writeln!(file, "{}", report::now_utc()))
+        .map_err(|e| format!("Failed to record unlock failure: {}", e))
+}
+
+// 로그의 첫 항목이 이 마스터 키로 열리는지 (키 교체 도중 중단되어 이전 키로 남은 로그를 찾을 때 사용)
+pub fn encrypted_with(vault_path: &Path, vault_key: &Key<Aes256Gcm>) -> bool {
+    let (log_path, _, _) = side_paths(vault_path);
+    let Ok(data) = fs::read(&log_path) else { return false };
+    let (records, _) = split_records(&data);
+    let cipher = Aes256Gcm::new(&subkey::derive(vault_key, Purpose::AuditLog));
+    records.first().is_some_and(|record| decrypt(&cipher, record, &0u64.to_be_bytes()).is_some())
+}
+
+// vault.key → vault.key.audit, vault.key.audit-head, vault.key.audit-pending
+pub fn side_paths(vault_path: &Path) -> (PathBuf, PathBuf, PathBuf) {
+    (
+        vault_file::side_path(vault_path, "audit"),
+        vault_file::side_path(vault_path, "audit-head"),
+        vault_file::side_path(vault_path, "audit-pending"),
+    )
+}
+
+// 완전한 항목들과, 그 항목들이 차지하는 바이트 수 (뒤에 남는 바이트는 불완전한 항목)
+fn split_records(data: &[u8]) -> (Vec<&[u8]>, usize) {
+    let mut records = Vec::new();
+    let mut offset = 0;
+    while data.len() - offset >= 4 {
+        let len = u32::from_be_bytes(data[offset..offset + 4].try_into().unwrap()) as usize;
+        let Some(record) = data.get(offset + 4..offset + 4 + len) else { break };
+        records.push(record);
+        offset += 4 + len;
+    }
+    (records, offset)
+}
+
+fn chain_hash(prev: &[u8; 32], record: &[u8]) -> [u8; 32] {
+    let mut hasher = Sha256::new();
+    hasher.update(prev);
+    hasher.update(record);
+    hasher.finalize().into()
+}
+
+// [논스] + [암호문], 항목 번호를 AAD 로 묶어서 다른 위치로 옮기면 복호화되지 않도록 함
+fn encrypt(cipher: &Aes256Gcm, plaintext: &[u8], aad: &[u8]) -> Result<Vec<u8>, String> {
+    let nonce = Aes256Gcm::generate_nonce(&mut OsRng);
+    let ciphertext = cipher.encrypt(&nonce, Payload { msg: plaintext, aad }).map_err(|e| e.to_string())?;
+    let mut record = nonce.to_vec();
+    record.extend_from_slice(&ciphertext);
+    Ok(record)
+}
+
+fn decrypt(cipher: &Aes256Gcm, record: &[u8], aad: &[u8]) -> Option<Vec<u8>> {
+    if record.len() < NONCE_LEN {
+        return None;
+    }
+    let (nonce, ciphertext) = record.split_at(NONCE_LEN);
+    cipher.decrypt(Nonce::from_slice(nonce), Payload { msg: ciphertext, aad }).ok()
+}
+
+// 마지막으로 기록한 항목 번호/해시와 실제 로그 끝이 같은지 확인
+fn check_head(cipher: &Aes256Gcm, head_path: &Path, count: u64, hash: &[u8; 32]) -> Option<String> {
+    let head = match fs::read(head_path) {
+        Ok(record) => decrypt(cipher, &record, HEAD_AAD).and_then(|plaintext| serde_json::from_slice::<AuditHead>(&plaintext).ok()),
+        Err(_) => return Some("The record of the last entry is missing.".to_string()),
+    };
+    match head {
+        None => Some("The record of the last entry could not be decrypted.".to_string()),
+        Some(head) if head.seq > count => Some(format!("{} entries were written but only {} remain. The log was truncated.", head.seq, count)),
+        Some(head) if head.seq != count || head.hash != to_hex(hash) => {
+            Some("The last entry does not match the recorded end of the log.".to_string())
+        }
+        Some(_) => None,
+    }
+}
+
+fn to_hex(bytes: &[u8]) -> String {
+    bytes.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn setup(name: &str) -> (PathBuf, Key<Aes256Gcm>, AuditLog) {
+        let dir = std::env::temp_dir().join(format!("audit-test-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&dir);
+        fs::create_dir_all(&dir).unwrap();
+        let vault_path = dir.join("vault.key");
+        let vault_key = Key::<Aes256Gcm>::from([5u8; 32]);
+        let audit = AuditLog::default();
+        audit.create(&vault_path, &vault_key).unwrap();
+        for event in [AuditEvent::VaultCreated, AuditEvent::UnlockSucceeded, AuditEvent::SettingsChanged] {
+            audit.record(event).unwrap();
+        }
+        audit.close();
+        (vault_path, vault_key, audit)
+    }
+
+    fn tamper_records(audit: &AuditLog) -> usize {
+        let view = audit.read().unwrap();
+        view.entries.iter().filter(|entry| matches!(entry.event, AuditEvent::LogTampered { .. })).count()
+    }
+
+    #[test]
+    fn reopening_an_intact_log_records_nothing() {
+        let (vault_path, vault_key, audit) = setup("intact");
+        audit.open(&vault_path, &vault_key).unwrap();
+        let view = audit.read().unwrap();
+        assert!(view.verified);
+        assert_eq!(view.entries.len(), 3);
+        let _ = fs::remove_dir_all(vault_path.parent().unwrap());
+    }
+
+    #[test]
+    fn cutting_records_is_recorded_before_appending() {
+        let (vault_path, vault_key, audit) = setup("cut");
+        let (log_path, _, _) = side_paths(&vault_path);
+        let data = fs::read(&log_path).unwrap();
+        let (records, _) = split_records(&data);
+        fs::write(&log_path, &data[..4 + records[0].len()]).unwrap();
+
+        audit.open(&vault_path, &vault_key).unwrap();
+        audit.record(AuditEvent::UnlockSucceeded).unwrap();
+        assert_eq!(tamper_records(&audit), 1);
+        let _ = fs::remove_dir_all(vault_path.parent().unwrap());
+    }
+
+    #[test]
+    fn editing_a_record_fails_verification() {
+        let (vault_path, vault_key, audit) = setup("edit");
+        let (log_path, _, _) = side_paths(&vault_path);
+        let mut data = fs::read(&log_path).unwrap();
+        let (records, _) = split_records(&data);
+        let offset = 4 + records[0].len() + 4 + NONCE_LEN + 1;
+        data[offset] ^= 1;
+        fs::write(&log_path, &data).unwrap();
+
+        audit.open(&vault_path, &vault_key).unwrap();
+        let view = audit.read().unwrap();
+        assert!(!view.verified);
+        assert_eq!(view.entries.len(), 1);
+        let _ = fs::remove_dir_all(vault_path.parent().unwrap());
+    }
+
+    #[test]
+    fn deleting_the_log_and_head_is_not_a_clean_log() {
+        let (vault_path, vault_key, audit) = setup("delete");
+        let (log_path, head_path, _) = side_paths(&vault_path);
+        fs::remove_file(&log_path).unwrap();
+        fs::remove_file(&head_path).unwrap();
+
+        audit.open(&vault_path, &vault_key).unwrap();
+        assert_eq!(tamper_records(&audit), 1);
+        let _ = fs::remove_dir_all(vault_path.parent().unwrap());
+    }
+}
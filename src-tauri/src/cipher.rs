@@ -0,0 +1,100 @@
+use aes_gcm::aead::generic_array::typenum::Unsigned;
+use aes_gcm::aead::{Aead, AeadCore, Nonce, OsRng, Payload};
+use aes_gcm::{Aes256Gcm, Key, KeyInit};
+use aes_gcm_siv::Aes256GcmSiv;
+use chacha20poly1305::XChaCha20Poly1305;
+use serde::{Deserialize, Serialize};
+
+/******************* 파일 내용 암호화 알고리즘 ******************/
+// 파일 헤더에 기록되며, 복호화할 때는 기록된 알고리즘을 사용함 (헤더가 없는 기존 파일은 AES-256-GCM)
+// - AES-256-GCM: AES-NI 가 있는 CPU 에서 가장 빠름. 무작위 96비트 논스라 같은 키로 아주 많은 파일을 암호화하면 논스 충돌 위험이 생김
+// - XChaCha20-Poly1305: 192비트 논스라 무작위 논스를 안심하고 쓸 수 있고, AES-NI 가 없는 CPU 에서도 빠름
+// - AES-256-GCM-SIV: 논스가 겹쳐도 같은 평문인지 여부 외에는 드러나지 않음 (nonce-misuse resistant)
+#[derive(Clone, Copy, Default, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Cipher {
+    #[default]
+    #[serde(rename = "AES-256-GCM")]
+    Aes256Gcm,
+    #[serde(rename = "XCHACHA20-POLY1305")]
+    XChaCha20Poly1305,
+    #[serde(rename = "AES-256-GCM-SIV")]
+    Aes256GcmSiv,
+}
+
+impl Cipher {
+    // 헤더에 기록되는 이름과 같음
+    pub fn name(self) -> &'static str {
+        match self {
+            Cipher::Aes256Gcm => "AES-256-GCM",
+            Cipher::XChaCha20Poly1305 => "XCHACHA20-POLY1305",
+            Cipher::Aes256GcmSiv => "AES-256-GCM-SIV",
+        }
+    }
+
+    pub fn nonce_len(self) -> usize {
+        match self {
+            Cipher::Aes256Gcm | Cipher::Aes256GcmSiv => 12,
+            Cipher::XChaCha20Poly1305 => 24,
+        }
+    }
+
+    // (논스, 암호문). aad 는 암호화하지 않고 함께 인증할 값 (비어 있으면 AAD 가 없는 것과 같음)
+    pub fn encrypt(self, key: &Key<Aes256Gcm>, plaintext: &[u8], aad: &[u8]) -> Result<(Vec<u8>, Vec<u8>), String> {
+        fn seal<C: KeyInit + Aead + AeadCore>(key: &Key<Aes256Gcm>, msg: &[u8], aad: &[u8]) -> Result<(Vec<u8>, Vec<u8>), String> {
+            let cipher = C::new_from_slice(key).map_err(|e| e.to_string())?;
+            let nonce = C::generate_nonce(&mut OsRng);
+            let ciphertext = cipher.encrypt(&nonce, Payload { msg, aad }).map_err(|e| e.to_string())?;
+            Ok((nonce.to_vec(), ciphertext))
+        }
+        match self {
+            Cipher::Aes256Gcm => seal::<Aes256Gcm>(key, plaintext, aad),
+            Cipher::XChaCha20Poly1305 => seal::<XChaCha20Poly1305>(key, plaintext, aad),
+            Cipher::Aes256GcmSiv => seal::<Aes256GcmSiv>(key, plaintext, aad),
+        }
+    }
+
+    // 키가 틀리거나, 내용이나 aad 가 바뀌었으면 None
+    pub fn decrypt(self, key: &Key<Aes256Gcm>, nonce: &[u8], ciphertext: &[u8], aad: &[u8]) -> Option<Vec<u8>> {
+        fn open<C: KeyInit + Aead>(key: &Key<Aes256Gcm>, nonce: &[u8], msg: &[u8], aad: &[u8]) -> Option<Vec<u8>> {
+            let cipher = C::new_from_slice(key).ok()?;
+            if nonce.len() != C::NonceSize::USIZE {
+                return None;
+            }
+            cipher.decrypt(Nonce::<C>::from_slice(nonce), Payload { msg, aad }).ok()
+        }
+        match self {
+            Cipher::Aes256Gcm => open::<Aes256Gcm>(key, nonce, ciphertext, aad),
+            Cipher::XChaCha20Poly1305 => open::<XChaCha20Poly1305>(key, nonce, ciphertext, aad),
+            Cipher::Aes256GcmSiv => open::<Aes256GcmSiv>(key, nonce, ciphertext, aad),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const ALL: [Cipher; 3] = [Cipher::Aes256Gcm, Cipher::XChaCha20Poly1305, Cipher::Aes256GcmSiv];
+
+    #[test]
+    fn roundtrip_for_every_cipher() {
+        let key = Key::<Aes256Gcm>::from([1u8; 32]);
+        for cipher in ALL {
+            let (nonce, ciphertext) = cipher.encrypt(&key, b"plaintext", b"aad").unwrap();
+            assert_eq!(nonce.len(), cipher.nonce_len());
+            assert_eq!(cipher.decrypt(&key, &nonce, &ciphertext, b"aad").as_deref(), Some(b"plaintext".as_slice()));
+        }
+    }
+
+    #[test]
+    fn wrong_key_aad_or_nonce_fails() {
+        let key = Key::<Aes256Gcm>::from([1u8; 32]);
+        let other_key = Key::<Aes256Gcm>::from([2u8; 32]);
+        for cipher in ALL {
+            let (nonce, ciphertext) = cipher.encrypt(&key, b"plaintext", b"aad").unwrap();
+            assert!(cipher.decrypt(&other_key, &nonce, &ciphertext, b"aad").is_none());
+            assert!(cipher.decrypt(&key, &nonce, &ciphertext, b"other").is_none());
+            assert!(cipher.decrypt(&key, &nonce[1..], &ciphertext, b"aad").is_none());
+        }
+    }
+}
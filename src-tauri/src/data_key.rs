@@ -0,0 +1,60 @@
+use aes_gcm::aead::{Aead, AeadCore, OsRng, Payload};
+use aes_gcm::{Aes256Gcm, Key, KeyInit, Nonce};
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
+use hkdf::Hkdf;
+use rand::RngCore;
+use sha2::Sha256;
+
+use crate::envelope::Stanza;
+use crate::subkey::{self, Purpose};
+
+// 마스터 키 식별자를 유도할 때 쓰는 용도 구분 문자열 (키가 아니라 8바이트 값이므로 subkey 를 쓰지 않음)
+const KEY_ID_INFO: &[u8] = b"securevault key id v1";
+const KEY_ID_LEN: usize = 8;
+
+/******************* 파일마다 만드는 데이터 키 ******************/
+// 파일의 메타데이터와 내용은 무작위 데이터 키로 암호화하고, 데이터 키만 마스터 키로 암호화해 헤더에 넣음
+// 마스터 키를 바꾸거나 공유 대상을 늘릴 때 본문은 그대로 두고 헤더만 다시 쓰면 됨
+pub fn generate() -> Key<Aes256Gcm> {
+    let mut key = [0u8; 32];
+    rand::rng().fill_bytes(&mut key);
+    Key::<Aes256Gcm>::from(key)
+}
+
+// 헤더에 기록하는 마스터 키 식별자 (키 자체는 드러나지 않도록 HKDF 로 유도한 값의 앞부분, hex)
+pub fn key_id(master_key: &Key<Aes256Gcm>) -> String {
+    let mut okm = [0u8; KEY_ID_LEN];
+    Hkdf::<Sha256>::new(None, master_key.as_slice())
+        .expand(KEY_ID_INFO, &mut okm)
+        .expect("8 bytes is a valid HKDF-SHA256 output length");
+    okm.iter().map(|b| format!("{:02x}", b)).collect()
+}
+
+// 키 식별자를 AAD 로 넣어, 다른 키의 항목으로 바꿔치기할 수 없게 함
+pub fn wrap(data_key: &Key<Aes256Gcm>, master_key: &Key<Aes256Gcm>) -> Result<Stanza, String> {
+    let key_id = key_id(master_key);
+    let cipher = Aes256Gcm::new(&subkey::derive(master_key, Purpose::DataKeyWrap));
+    let nonce = Aes256Gcm::generate_nonce(&mut OsRng);
+    let wrapped_key = cipher
+        .encrypt(&nonce, Payload { msg: data_key.as_slice(), aad: key_id.as_bytes() })
+        .map_err(|e| e.to_string())?;
+    Ok(Stanza::Vault { key_id, nonce: BASE64.encode(nonce), wrapped_key: BASE64.encode(wrapped_key) })
+}
+
+// 이 마스터 키로 암호화한 항목이 아니면 None
+pub fn unwrap(stanza: &Stanza, master_key: &Key<Aes256Gcm>) -> Option<Vec<u8>> {
+    let Stanza::Vault { key_id: stanza_key_id, nonce, wrapped_key } = stanza else { return None };
+    if *stanza_key_id != key_id(master_key) {
+        return None;
+    }
+    let nonce = BASE64.decode(nonce).ok()?;
+    if nonce.len() != 12 {
+        return None;
+    }
+    let wrapped_key = BASE64.decode(wrapped_key).ok()?;
+    let cipher = Aes256Gcm::new(&subkey::derive(master_key, Purpose::DataKeyWrap));
+    cipher
+        .decrypt(Nonce::from_slice(&nonce), Payload { msg: &wrapped_key, aad: stanza_key_id.as_bytes() })
+        .ok()
+}
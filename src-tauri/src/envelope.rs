@@ -0,0 +1,266 @@
+use aes_gcm::{Aes256Gcm, Key};
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+
+use crate::cipher::Cipher;
+use crate::passphrase::KdfParams;
+use crate::subkey::{self, Purpose};
+
+// 수신자 헤더가 있는 파일의 시작 부분. 기존 .enc 파일은 무작위 논스로 시작하므로 헤더가 없는 것으로 봄
+const MAGIC: &[u8; 5] = b"SVLT\x02";
+// 본문을 헤더/파일 ID 에 묶는 AAD 의 용도 구분 문자열
+const METADATA_AAD_LABEL: &[u8] = b"securevault metadata v1";
+const CONTENT_AAD_LABEL: &[u8] = b"securevault content v1";
+
+// 파일 키를 수신자 한 명(또는 비밀번호)을 위해 암호화한 항목
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(tag = "type", rename_all_fields = "camelCase")]
+pub enum Stanza {
+    // 이 vault 의 마스터 키로 파일 키를 암호화 (keyId 로 어떤 마스터 키인지 구분, 값은 base64)
+    Vault {
+        key_id: String,
+        nonce: String,
+        wrapped_key: String,
+    },
+    // 임시 X25519 키와 수신자 공개키로 만든 공유 비밀에서 유도한 키로 파일 키를 암호화 (값은 모두 base64)
+    X25519 {
+        ephemeral: String,
+        nonce: String,
+        wrapped_key: String,
+    },
+    // vault 와 상관없는 비밀번호로 Argon2id 를 돌려 만든 키로 파일 키를 암호화 (팀 밖의 사람에게 전달할 때)
+    Password {
+        salt: String,
+        params: KdfParams,
+        nonce: String,
+        wrapped_key: String,
+    },
+}
+
+// 본문을 암호화하는 키를 파일 키에서 어떻게 얻는지
+#[derive(Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub enum KeyDerivation {
+    // 파일 키(이전 형식은 vault 키)를 메타데이터와 내용에 그대로 사용
+    #[default]
+    #[serde(rename = "NONE")]
+    None,
+    // 파일 키에서 HKDF-SHA256 으로 메타데이터 키와 내용 키를 따로 유도
+    #[serde(rename = "HKDF-SHA256")]
+    HkdfSha256,
+}
+
+// 헤더가 없는 기존 형식 파일은 기본값(AES-256-GCM, 파일 ID 없음, 키 유도 없음)의 헤더로 처리
+#[derive(Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct FileHeader {
+    // 본문 암호화 알고리즘 (이 항목이 없는 헤더는 AES-256-GCM)
+    #[serde(default)]
+    pub cipher: Cipher,
+    // 파일마다 만든 무작위 ID (hex). 없으면 본문을 AAD 없이 암호화한 이전 형식
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub file_id: Option<String>,
+    // 이 항목이 없는 헤더는 파일 키를 그대로 사용 (파일 ID 가 없는 이전 형식만 해당)
+    #[serde(default, skip_serializing_if = "is_direct")]
+    pub key_derivation: KeyDerivation,
+    // 헤더가 있는 파일은 항상 수신자가 하나 이상 있음 (validate)
+    pub recipients: Vec<Stanza>,
+}
+
+impl FileHeader {
+    // 새 파일의 헤더 (파일 ID 를 새로 만듦)
+    pub fn new(cipher: Cipher, recipients: Vec<Stanza>) -> Self {
+        let mut file_id = [0u8; 16];
+        rand::rng().fill_bytes(&mut file_id);
+        let file_id = file_id.iter().map(|b| format!("{:02x}", b)).collect();
+        FileHeader { cipher, file_id: Some(file_id), key_derivation: KeyDerivation::HkdfSha256, recipients }
+    }
+
+    /******************* 본문을 헤더와 파일 ID 에 묶는 AAD ******************/
+    // 메타데이터: 알고리즘 + 파일 ID + 키 유도 방식, 내용: 여기에 암호화된 메타데이터 블록(논스 포함)의 SHA-256 을 더함
+    // 그래서 다른 파일의 메타데이터나 내용을 붙여 넣거나 두 파일의 내용을 맞바꾸거나 헤더의 알고리즘/키 유도 방식을 바꾸면 복호화에 실패함
+    // 수신자 항목은 키 교체나 공유 때 본문을 건드리지 않고 다시 쓰도록 일부러 묶지 않음 (각 항목은 파일 키를 AEAD 로 암호화하므로 바꿔치면 본문이 열리지 않음)
+    // 파일 ID 가 없는 이전 형식은 AAD 가 비어 있음 (AAD 없이 암호화한 것과 같음)
+    // 파일 ID 가 있는 헤더는 항상 HKDF-SHA256 으로 키를 유도함 (check_key_derivation)
+    fn metadata_aad(&self) -> Vec<u8> {
+        self.associated_data(METADATA_AAD_LABEL, &[])
+    }
+
+    fn content_aad(&self, sealed_metadata: &[u8]) -> Vec<u8> {
+        self.associated_data(CONTENT_AAD_LABEL, &Sha256::digest(sealed_metadata))
+    }
+
+    fn associated_data(&self, label: &[u8], extra: &[u8]) -> Vec<u8> {
+        let Some(file_id) = &self.file_id else { return Vec::new() };
+        let mut aad = Vec::new();
+        for part in [label, self.cipher.name().as_bytes(), file_id.as_bytes(), self.key_derivation.name().as_bytes()] {
+            aad.extend_from_slice(part);
+            aad.push(0);
+        }
+        aad.extend_from_slice(extra);
+        aad
+    }
+
+    // (메타데이터 키, 내용 키)
+    fn body_keys(&self, key: &Key<Aes256Gcm>) -> (Key<Aes256Gcm>, Key<Aes256Gcm>) {
+        match self.key_derivation {
+            KeyDerivation::None => (*key, *key),
+            KeyDerivation::HkdfSha256 => (subkey::derive(key, Purpose::Metadata), subkey::derive(key, Purpose::Content)),
+        }
+    }
+
+    // 읽은 헤더가 이 앱이 만드는 형식인지 확인. 파일 키 없이 마스터 키로 바로 암호화된 본문은 헤더 없는 기존 형식만 있음
+    pub fn validate(&self) -> Result<(), String> {
+        if self.recipients.is_empty() {
+            return Err("Invalid file: header has no recipients".into());
+        }
+        self.check_key_derivation()
+    }
+
+    // 파일 ID 는 키 유도 방식과 함께 도입되었으므로, 파일 ID 가 있는데 키를 유도하지 않는 헤더는 만들어지지 않음
+    fn check_key_derivation(&self) -> Result<(), String> {
+        if self.file_id.is_some() && self.key_derivation != KeyDerivation::HkdfSha256 {
+            return Err("Invalid file: unsupported key derivation for this header".into());
+        }
+        Ok(())
+    }
+
+    pub fn has_password(&self) -> bool {
+        self.recipients.iter().any(|stanza| matches!(stanza, Stanza::Password { .. }))
+    }
+}
+
+impl KeyDerivation {
+    // 헤더에 기록되는 이름과 같음
+    pub fn name(self) -> &'static str {
+        match self {
+            KeyDerivation::None => "NONE",
+            KeyDerivation::HkdfSha256 => "HKDF-SHA256",
+        }
+    }
+}
+
+fn is_direct(key_derivation: &KeyDerivation) -> bool {
+    *key_derivation == KeyDerivation::None
+}
+
+/******************* 암호화된 파일 형식 ******************/
+// 기존 형식: [본문]                                   (본문은 vault 키와 AES-256-GCM 으로 암호화)
+// 헤더 형식: [MAGIC] + [헤더 길이(u32)] + [헤더 JSON] + [본문]   (본문은 헤더에 기록된 알고리즘과 파일마다 만든 무작위 키로 암호화)
+// 본문: [메타데이터 논스] + [메타데이터 길이(u16)] + [암호화된 메타데이터] + [내용 논스] + [암호화된 내용]  (논스 길이는 알고리즘에 따라 12 또는 24)
+// 메타데이터와 내용은 헤더의 파일 ID 와 서로에게 AAD 로 묶여 있음 (FileHeader::metadata_aad/content_aad)
+// 새 파일은 메타데이터와 내용을 파일 키에서 유도한 서로 다른 키로 암호화함 (FileHeader::body_keys)
+pub fn write_header(header: &FileHeader) -> Result<Vec<u8>, String> {
+    let header_bytes = serde_json::to_vec(header).map_err(|e| e.to_string())?;
+    let mut data = Vec::with_capacity(MAGIC.len() + 4 + header_bytes.len());
+    data.extend_from_slice(MAGIC);
+    data.extend_from_slice(&(header_bytes.len() as u32).to_be_bytes());
+    data.extend_from_slice(&header_bytes);
+    Ok(data)
+}
+
+// 수신자 헤더가 없으면 None (기존 형식으로 처리)
+pub fn read_header(data: &[u8]) -> Option<(FileHeader, &[u8])> {
+    let rest = data.strip_prefix(MAGIC.as_slice())?;
+    let header_len = u32::from_be_bytes(rest.get(..4)?.try_into().ok()?) as usize;
+    let header_bytes = rest.get(4..4 + header_len)?;
+    let header = serde_json::from_slice(header_bytes).ok()?;
+    Some((header, &rest[4 + header_len..]))
+}
+
+pub fn seal_body(header: &FileHeader, key: &Key<Aes256Gcm>, metadata_bytes: &[u8], content: &[u8]) -> Result<Vec<u8>, String> {
+    let cipher = header.cipher;
+    let (metadata_key, content_key) = header.body_keys(key);
+    let (metadata_nonce, encrypted_metadata) = cipher.encrypt(&metadata_key, metadata_bytes, &header.metadata_aad())?;
+
+    let mut body = Vec::with_capacity(2 * cipher.nonce_len() + 2 + encrypted_metadata.len() + content.len() + 16);
+    body.extend_from_slice(&metadata_nonce);
+    body.extend_from_slice(&(encrypted_metadata.len() as u16).to_be_bytes());
+    body.extend_from_slice(&encrypted_metadata);
+    let (content_nonce, encrypted_content) = cipher.encrypt(&content_key, content, &header.content_aad(&body))?;
+    body.extend_from_slice(&content_nonce);
+    body.extend_from_slice(&encrypted_content);
+    Ok(body)
+}
+
+// (메타데이터, 내용) 을 복호화해서 반환. 헤더가 없는 기존 형식은 FileHeader::default() 를 넘김
+pub fn open_body(header: &FileHeader, key: &Key<Aes256Gcm>, body: &[u8]) -> Result<(Vec<u8>, Vec<u8>), String> {
+    header.check_key_derivation()?;
+    let cipher = header.cipher;
+    let nonce_len = cipher.nonce_len();
+    if body.len() < nonce_len + 2 { return Err("Invalid file: too short for header".into()); }
+    let metadata_nonce = &body[..nonce_len];
+    let encrypted_metadata_len = u16::from_be_bytes([body[nonce_len], body[nonce_len + 1]]) as usize;
+    let metadata_end = nonce_len + 2 + encrypted_metadata_len;
+    if body.len() < metadata_end + nonce_len { return Err("Invalid file: metadata length mismatch".into()); }
+    let encrypted_metadata = &body[nonce_len + 2..metadata_end];
+    let content_nonce = &body[metadata_end..metadata_end + nonce_len];
+    let encrypted_content = &body[metadata_end + nonce_len..];
+
+    let (metadata_key, content_key) = header.body_keys(key);
+    let metadata_bytes = cipher
+        .decrypt(&metadata_key, metadata_nonce, encrypted_metadata, &header.metadata_aad())
+        .ok_or("Decryption failed: metadata corrupt")?;
+    let content = cipher
+        .decrypt(&content_key, content_nonce, encrypted_content, &header.content_aad(&body[..metadata_end]))
+        .ok_or("Decryption failed: content corrupt")?;
+    Ok((metadata_bytes, content))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    // (메타데이터 블록, 내용 블록)
+    fn split_body(header: &FileHeader, body: &[u8]) -> (Vec<u8>, Vec<u8>) {
+        let nonce_len = header.cipher.nonce_len();
+        let metadata_end = nonce_len + 2 + u16::from_be_bytes([body[nonce_len], body[nonce_len + 1]]) as usize;
+        (body[..metadata_end].to_vec(), body[metadata_end..].to_vec())
+    }
+
+    #[test]
+    fn seal_and_open_body() {
+        let key = Key::<Aes256Gcm>::from([7u8; 32]);
+        for cipher in [Cipher::Aes256Gcm, Cipher::XChaCha20Poly1305, Cipher::Aes256GcmSiv] {
+            let header = FileHeader::new(cipher, Vec::new());
+            let body = seal_body(&header, &key, b"{\"originalFilename\":\"a.txt\"}", b"hello").unwrap();
+            let (metadata, content) = open_body(&header, &key, &body).unwrap();
+            assert_eq!(metadata, b"{\"originalFilename\":\"a.txt\"}");
+            assert_eq!(content, b"hello");
+        }
+    }
+
+    #[test]
+    fn moving_blocks_between_files_fails() {
+        let key = Key::<Aes256Gcm>::from([7u8; 32]);
+        let first = FileHeader::new(Cipher::default(), Vec::new());
+        let second = FileHeader::new(Cipher::default(), Vec::new());
+        let (first_metadata, first_content) = split_body(&first, &seal_body(&first, &key, b"first", b"first content").unwrap());
+        let (second_metadata, second_content) = split_body(&second, &seal_body(&second, &key, b"second", b"second content").unwrap());
+
+        // 다른 파일의 메타데이터
+        assert!(open_body(&first, &key, &[second_metadata.as_slice(), &first_content].concat()).is_err());
+        // 다른 파일의 내용
+        assert!(open_body(&first, &key, &[first_metadata.as_slice(), &second_content].concat()).is_err());
+        // 다른 파일의 본문 전체를 이 헤더로 열기
+        assert!(open_body(&first, &key, &[second_metadata.as_slice(), &second_content].concat()).is_err());
+    }
+
+    #[test]
+    fn changing_the_header_fails() {
+        let key = Key::<Aes256Gcm>::from([7u8; 32]);
+        let header = FileHeader::new(Cipher::Aes256GcmSiv, Vec::new());
+        let body = seal_body(&header, &key, b"metadata", b"content").unwrap();
+        let file_id = header.file_id.clone();
+
+        let other_file_id = FileHeader { file_id: Some("00".repeat(16)), ..FileHeader::new(Cipher::Aes256GcmSiv, Vec::new()) };
+        assert!(open_body(&other_file_id, &key, &body).is_err());
+        // 논스 길이가 같은 알고리즘으로 바꿔도 AAD 가 달라서 실패
+        let other_cipher = FileHeader { cipher: Cipher::Aes256Gcm, file_id: file_id.clone(), ..FileHeader::new(Cipher::Aes256Gcm, Vec::new()) };
+        assert!(open_body(&other_cipher, &key, &body).is_err());
+        // 파일 ID 가 있는데 키 유도를 지운 헤더는 복호화를 시도하기 전에 거부
+        let other_derivation = FileHeader { key_derivation: KeyDerivation::None, file_id, ..FileHeader::new(Cipher::Aes256GcmSiv, Vec::new()) };
+        assert!(other_derivation.check_key_derivation().is_err());
+        assert!(open_body(&other_derivation, &key, &body).unwrap_err().starts_with("Invalid file"));
+    }
+}
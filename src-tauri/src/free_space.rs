@@ -0,0 +1,104 @@
+use rand::RngCore;
+use std::fs::{self, File};
+use std::io::{ErrorKind, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use uuid::Uuid;
+
+use crate::storage;
+
+const CHUNK_SIZE: usize = 1024 * 1024;
+// 임시 파일 하나의 최대 크기 (파일 시스템별 최대 파일 크기 제한을 피하기 위함, FAT32 는 4GB)
+const MAX_FILL_FILE_SIZE: u64 = 1024 * 1024 * 1024;
+// 남은 공간을 다시 확인하는 간격
+const RECHECK_INTERVAL: u64 = 64 * 1024 * 1024;
+// 여유 공간을 지정하지 않았을 때 남겨둘 기본 크기
+pub const DEFAULT_RESERVE_BYTES: u64 = 256 * 1024 * 1024;
+
+/******************* 선택한 폴더가 있는 볼륨의 빈 공간을 무작위 데이터로 채운 뒤 삭제 ******************/
+// 이미 삭제된 파일이 남아 있던 빈 블록을 덮어쓰기 위함
+// reserve_bytes 만큼은 비워 두어 디스크가 완전히 가득 차지 않도록 하고,
+// 취소/오류/완료 여부와 관계없이 만든 임시 파일은 모두 삭제함
+// on_progress 에는 (지금까지 쓴 바이트, 쓸 예정인 전체 바이트)를 전달
+pub fn wipe_free_space(
+    dir: &Path,
+    reserve_bytes: u64,
+    cancel_flag: &AtomicBool,
+    on_progress: &mut dyn FnMut(u64, u64),
+) -> Result<u64, String> {
+    if !dir.is_dir() {
+        return Err("Not a directory".to_string());
+    }
+    let initial_available = storage::available_space(dir)?;
+    let total_to_write = initial_available.saturating_sub(reserve_bytes);
+
+    let work_dir = dir.join(format!(".securevault-wipe-{}", Uuid::new_v4()));
+    fs::create_dir(&work_dir).map_err(|e| e.to_string())?;
+
+    let result = fill(&work_dir, reserve_bytes, total_to_write, cancel_flag, on_progress);
+    let cleanup = fs::remove_dir_all(&work_dir).map_err(|e| format!("Failed to remove temporary files: {}", e));
+
+    let written = result?;
+    cleanup?;
+    Ok(written)
+}
+
+fn fill(
+    work_dir: &Path,
+    reserve_bytes: u64,
+    total_to_write: u64,
+    cancel_flag: &AtomicBool,
+    on_progress: &mut dyn FnMut(u64, u64),
+) -> Result<u64, String> {
+    let mut buffer = vec![0u8; CHUNK_SIZE];
+    let mut written_bytes = 0u64;
+    let mut file_index = 0;
+
+    loop {
+        let mut remaining = storage::available_space(work_dir)?.saturating_sub(reserve_bytes);
+        if remaining == 0 {
+            return Ok(written_bytes);
+        }
+
+        let file_path: PathBuf = work_dir.join(format!("fill-{:05}.tmp", file_index));
+        file_index += 1;
+        let mut file = File::create(&file_path).map_err(|e| e.to_string())?;
+        let mut file_written = 0u64;
+        let mut since_recheck = 0u64;
+
+        while file_written < MAX_FILL_FILE_SIZE && remaining > 0 {
+            if cancel_flag.load(Ordering::SeqCst) {
+                return Err("Operation cancelled.".to_string());
+            }
+            rand::rng().fill_bytes(&mut buffer);
+            let bytes_to_write = [CHUNK_SIZE as u64, remaining, MAX_FILL_FILE_SIZE - file_written]
+                .into_iter()
+                .min()
+                .unwrap_or(0) as usize;
+
+            match file.write_all(&buffer[..bytes_to_write]) {
+                Ok(_) => {}
+                // 다른 프로그램이 공간을 사용해서 예상보다 먼저 가득 찬 경우는 정상 종료로 처리
+                Err(e) if e.kind() == ErrorKind::StorageFull => {
+                    let _ = file.sync_all();
+                    return Ok(written_bytes);
+                }
+                Err(e) => return Err(e.to_string()),
+            }
+
+            file_written += bytes_to_write as u64;
+            written_bytes += bytes_to_write as u64;
+            remaining -= bytes_to_write as u64;
+            since_recheck += bytes_to_write as u64;
+            on_progress(written_bytes, total_to_write.max(written_bytes));
+
+            // 다른 프로그램의 사용량 변화를 반영하기 위해 주기적으로 남은 공간을 다시 확인
+            if since_recheck >= RECHECK_INTERVAL {
+                since_recheck = 0;
+                remaining = storage::available_space(work_dir)?.saturating_sub(reserve_bytes);
+            }
+        }
+        // 캐시에만 남지 않고 실제 빈 블록에 기록되도록 동기화
+        file.sync_all().map_err(|e| e.to_string())?;
+    }
+}
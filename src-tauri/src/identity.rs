@@ -0,0 +1,101 @@
+use aes_gcm::aead::{Aead, AeadCore, OsRng};
+use aes_gcm::{Aes256Gcm, Key, KeyInit, Nonce};
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
+use bech32::{Bech32, Hrp};
+use hkdf::Hkdf;
+use rand::RngCore;
+use sha2::Sha256;
+use x25519_dalek::{PublicKey, StaticSecret};
+
+use crate::envelope::Stanza;
+
+// age 와 같은 키 문자열 형식 (age1... / AGE-SECRET-KEY-1...) 을 사용하므로 age 사용자와 공개키를 주고받을 수 있음
+const PUBLIC_KEY_HRP: &str = "age";
+const SECRET_KEY_HRP: &str = "age-secret-key-";
+const WRAP_KEY_INFO: &[u8] = b"securevault x25519 v1";
+
+/******************* 새 X25519 키 쌍 생성 ******************/
+// (비밀키 문자열, 공개키 문자열)
+pub fn generate() -> (String, String) {
+    let mut secret_bytes = [0u8; 32];
+    rand::rng().fill_bytes(&mut secret_bytes);
+    let secret = StaticSecret::from(secret_bytes);
+    let public = PublicKey::from(&secret);
+    let secret_text = bech32::encode_upper::<Bech32>(Hrp::parse_unchecked(SECRET_KEY_HRP), secret.as_bytes())
+        .expect("32-byte key fits in a bech32 string");
+    (secret_text, encode_public_key(&public))
+}
+
+pub fn encode_public_key(public: &PublicKey) -> String {
+    bech32::encode::<Bech32>(Hrp::parse_unchecked(PUBLIC_KEY_HRP), public.as_bytes())
+        .expect("32-byte key fits in a bech32 string")
+}
+
+pub fn parse_public_key(text: &str) -> Result<PublicKey, String> {
+    let bytes = decode_key(text.trim(), PUBLIC_KEY_HRP).ok_or("Invalid public key")?;
+    Ok(PublicKey::from(bytes))
+}
+
+pub fn parse_secret_key(text: &str) -> Result<StaticSecret, String> {
+    let bytes = decode_key(text.trim(), SECRET_KEY_HRP).ok_or("Invalid secret key")?;
+    Ok(StaticSecret::from(bytes))
+}
+
+fn decode_key(text: &str, expected_hrp: &str) -> Option<[u8; 32]> {
+    let (hrp, data) = bech32::decode(text).ok()?;
+    if !hrp.as_str().eq_ignore_ascii_case(expected_hrp) {
+        return None;
+    }
+    data.try_into().ok()
+}
+
+/******************* 파일 키를 수신자 공개키로 암호화 ******************/
+// 파일마다 임시 키 쌍을 만들어 수신자 공개키와 X25519 로 공유 비밀을 만들고,
+// HKDF(솔트 = 임시 공개키 + 수신자 공개키) 로 유도한 키로 파일 키를 암호화
+pub fn wrap_file_key(file_key: &[u8], recipient: &PublicKey) -> Result<Stanza, String> {
+    let mut ephemeral_bytes = [0u8; 32];
+    rand::rng().fill_bytes(&mut ephemeral_bytes);
+    let ephemeral = StaticSecret::from(ephemeral_bytes);
+    let ephemeral_public = PublicKey::from(&ephemeral);
+    let shared = ephemeral.diffie_hellman(recipient);
+    if !shared.was_contributory() {
+        return Err("Invalid public key".into());
+    }
+
+    let cipher = Aes256Gcm::new(&derive_wrap_key(shared.as_bytes(), &ephemeral_public, recipient));
+    let nonce = Aes256Gcm::generate_nonce(&mut OsRng);
+    let wrapped_key = cipher.encrypt(&nonce, file_key).map_err(|e| e.to_string())?;
+    Ok(Stanza::X25519 {
+        ephemeral: BASE64.encode(ephemeral_public.as_bytes()),
+        nonce: BASE64.encode(nonce),
+        wrapped_key: BASE64.encode(wrapped_key),
+    })
+}
+
+// 이 비밀키를 위한 항목이 아니면 None
+pub fn unwrap_file_key(stanza: &Stanza, secret: &StaticSecret) -> Option<Vec<u8>> {
+    let Stanza::X25519 { ephemeral, nonce, wrapped_key } = stanza else { return None };
+    let ephemeral_bytes: [u8; 32] = BASE64.decode(ephemeral).ok()?.try_into().ok()?;
+    let nonce_bytes = BASE64.decode(nonce).ok()?;
+    if nonce_bytes.len() != 12 {
+        return None;
+    }
+    let wrapped_key = BASE64.decode(wrapped_key).ok()?;
+
+    let ephemeral_public = PublicKey::from(ephemeral_bytes);
+    let shared = secret.diffie_hellman(&ephemeral_public);
+    let cipher = Aes256Gcm::new(&derive_wrap_key(shared.as_bytes(), &ephemeral_public, &PublicKey::from(secret)));
+    cipher.decrypt(Nonce::from_slice(&nonce_bytes), wrapped_key.as_slice()).ok()
+}
+
+fn derive_wrap_key(shared: &[u8], ephemeral: &PublicKey, recipient: &PublicKey) -> Key<Aes256Gcm> {
+    let mut salt = [0u8; 64];
+    salt[..32].copy_from_slice(ephemeral.as_bytes());
+    salt[32..].copy_from_slice(recipient.as_bytes());
+    let mut okm = [0u8; 32];
+    Hkdf::<Sha256>::new(Some(&salt), shared)
+        .expand(WRAP_KEY_INFO, &mut okm)
+        .expect("32 bytes is a valid HKDF-SHA256 output length");
+    Key::<Aes256Gcm>::from(okm)
+}
@@ -0,0 +1,318 @@
+use serde::Serialize;
+use std::collections::HashMap;
+use std::path::PathBuf;
+use std::sync::Mutex;
+use std::time::Instant;
+use tauri::ipc::Channel;
+use uuid::Uuid;
+
+use crate::audit::{AuditEvent, AuditLog};
+use crate::progress::ProgressTracker;
+use crate::report::{self, EntryStatus, JobReport, JobStatus, ReportEntry};
+use crate::EachFile;
+
+// 작업 하나의 진행 상황을 프론트엔드 채널로 보내는 메시지
+// { "event": "fileStarted", "data": { ... } } 형태로 직렬화됨
+#[derive(Clone, Serialize)]
+#[serde(rename_all = "camelCase", rename_all_fields = "camelCase", tag = "event", content = "data")]
+pub enum JobEvent {
+    Started {
+        job_id: String,
+        number_of_files: usize,
+        total_bytes: u64,
+    },
+    FileStarted {
+        path: String,
+        file_number: usize,
+        size: u64,
+    },
+    // error 가 있으면 실패한 항목
+    FileFinished {
+        file: EachFile,
+    },
+    Progress {
+        current_file_path: String,
+        current_file_number: usize,
+        number_of_files: usize,
+        total_progress: f64, // 전체 진행률 (0.0 ~ 1.0)
+        processed_bytes: u64,
+        total_bytes: u64,
+        bytes_per_second: f64,
+        eta_seconds: Option<f64>, // 처리 속도를 아직 알 수 없으면 None
+    },
+    // 취소 시점까지 처리한 항목과 시작하지 못한 항목
+    // output_count 가 0 보다 크면 remove_job_outputs 로 이미 만들어진 파일을 지울 수 있음
+    Cancelled {
+        job_id: String,
+        succeeded: Vec<String>,
+        failed: Vec<String>,
+        not_started: Vec<String>,
+        output_count: usize,
+    },
+    // 트랜잭션 모드에서 실패/취소로 결과를 반영하지 않은 경우
+    // discarded 는 처리에 성공했지만 되돌린 항목, not_started 는 실패 후 처리하지 않은 항목
+    RolledBack {
+        reason: String,
+        discarded: Vec<String>,
+        not_started: Vec<String>,
+    },
+    Finished {
+        succeeded: usize,
+        failed: usize,
+        processed_bytes: u64,
+    },
+}
+
+/******************* 작업 하나의 진행 상황을 채널로 전송 ******************/
+// 창이 닫히는 등 전송에 실패하면 이후 전송은 생략하고 작업은 그대로 계속함
+// 작업이 끝나면 처리 기록을 JobRegistry 에 남겨서 export_job_report 로 내보낼 수 있게 하고, 감사 로그에도 요약을 남김
+pub struct JobReporter<'a> {
+    channel: Channel<JobEvent>,
+    registry: &'a JobRegistry,
+    audit: &'a AuditLog,
+    job_id: String,
+    operation: &'static str,
+    started_at: String,
+    started: Instant,
+    tracker: ProgressTracker,
+    number_of_files: usize,
+    current_file_path: String,
+    current_file_number: usize,
+    current_file_started: Instant,
+    succeeded: Vec<String>,
+    failed: Vec<String>,
+    outputs: Vec<PathBuf>,
+    entries: Vec<ReportEntry>,
+    reason: Option<String>,
+    problems: Vec<String>,
+    disconnected: bool,
+}
+
+impl<'a> JobReporter<'a> {
+    pub fn start(
+        channel: Channel<JobEvent>,
+        registry: &'a JobRegistry,
+        audit: &'a AuditLog,
+        operation: &'static str,
+        number_of_files: usize,
+        total_bytes: u64,
+    ) -> Self {
+        let mut reporter = JobReporter {
+            channel,
+            registry,
+            audit,
+            job_id: Uuid::new_v4().to_string(),
+            operation,
+            started_at: report::now_utc(),
+            started: Instant::now(),
+            tracker: ProgressTracker::new(total_bytes),
+            number_of_files,
+            current_file_path: String::new(),
+            current_file_number: 0,
+            current_file_started: Instant::now(),
+            succeeded: Vec::new(),
+            failed: Vec::new(),
+            outputs: Vec::new(),
+            entries: Vec::new(),
+            reason: None,
+            problems: Vec::new(),
+            disconnected: false,
+        };
+        reporter.send(JobEvent::Started { job_id: reporter.job_id.clone(), number_of_files, total_bytes });
+        reporter
+    }
+
+    fn send(&mut self, event: JobEvent) {
+        if self.disconnected {
+            return;
+        }
+        if let Err(e) = self.channel.send(event) {
+            self.problems.push(format!("Progress updates stopped: {}", e));
+            self.disconnected = true;
+        }
+    }
+
+    pub fn job_id(&self) -> &str {
+        &self.job_id
+    }
+
+    // 지금까지 시작한 항목 수 (취소 시 시작하지 못한 항목을 구할 때 사용)
+    pub fn files_started(&self) -> usize {
+        self.current_file_number
+    }
+
+    pub fn file_started(&mut self, path: &str, size: u64) {
+        self.current_file_path = path.to_string();
+        self.current_file_number += 1;
+        self.current_file_started = Instant::now();
+        self.send(JobEvent::FileStarted {
+            path: path.to_string(),
+            file_number: self.current_file_number,
+            size,
+        });
+    }
+
+    // 지금 처리 중인 파일에서 끝난 바이트 수 (파일 크기 기준)
+    pub fn file_progress(&mut self, bytes: u64) {
+        self.tracker.set_current(bytes);
+        self.emit_progress();
+    }
+
+    // 빈 공간 삭제처럼 작업 중에 전체 크기가 바뀌는 경우
+    pub fn set_total(&mut self, total_bytes: u64) {
+        self.tracker.set_total(total_bytes);
+    }
+
+    // 처리한 항목의 결과를 보내고, size 만큼 전체 진행에 반영
+    // 크기, 걸린 시간, 오류 코드는 여기서 채움
+    pub fn file_finished(&mut self, mut file: EachFile, size: u64) {
+        file.size = size;
+        file.duration_ms = self.current_file_started.elapsed().as_millis() as u64;
+        self.record(&mut file);
+        self.send(JobEvent::FileFinished { file });
+        self.tracker.finish_file(size);
+        self.emit_progress();
+    }
+
+    // 작업을 시작하기 전에 실패한 항목 (폴더를 읽을 수 없는 경우 등)
+    pub fn file_failed_early(&mut self, mut file: EachFile) {
+        self.record(&mut file);
+        self.send(JobEvent::FileFinished { file });
+    }
+
+    fn record(&mut self, file: &mut EachFile) {
+        let status = match &file.error {
+            Some(error) => {
+                file.error_code = Some(report::error_code(error).to_string());
+                self.failed.push(file.path.clone());
+                EntryStatus::Failed
+            }
+            None => {
+                self.succeeded.push(file.path.clone());
+                self.outputs.extend(file.output.as_ref().map(PathBuf::from));
+                EntryStatus::Succeeded
+            }
+        };
+        self.entries.push(ReportEntry::from_file(file, status));
+    }
+
+    // 마지막 전송 후 일정 시간이 지났을 때만 진행 상황을 보냄
+    fn emit_progress(&mut self) {
+        if !self.tracker.should_emit() {
+            return;
+        }
+        let stats = self.tracker.stats();
+        self.send(JobEvent::Progress {
+            current_file_path: self.current_file_path.clone(),
+            current_file_number: self.current_file_number,
+            number_of_files: self.number_of_files,
+            total_progress: stats.progress,
+            processed_bytes: stats.processed_bytes,
+            total_bytes: stats.total_bytes,
+            bytes_per_second: stats.bytes_per_second,
+            eta_seconds: stats.eta_seconds,
+        });
+    }
+
+    // 성공했던 항목도 반영되지 않았으므로 실패로 옮기고, 지울 출력 파일도 없음
+    pub fn rolled_back(&mut self, reason: String, not_started: Vec<String>) {
+        let discarded = std::mem::take(&mut self.succeeded);
+        self.failed.extend(discarded.iter().cloned());
+        self.outputs.clear();
+        for entry in self.entries.iter_mut().filter(|e| e.status == EntryStatus::Succeeded) {
+            entry.status = EntryStatus::RolledBack;
+            entry.output_path = None;
+        }
+        self.entries.extend(not_started.iter().cloned().map(ReportEntry::not_started));
+        self.reason = Some(reason.clone());
+        self.send(JobEvent::RolledBack { reason, discarded, not_started });
+    }
+
+    pub fn finish(mut self) {
+        let event = JobEvent::Finished {
+            succeeded: self.succeeded.len(),
+            failed: self.failed.len(),
+            processed_bytes: self.tracker.stats().processed_bytes,
+        };
+        self.send(event);
+        let status = if self.reason.is_some() { JobStatus::RolledBack } else { JobStatus::Completed };
+        self.store_report(status);
+    }
+
+    // 취소 요약을 보내고, 이미 만들어진 출력 파일은 remove_job_outputs 로 지울 수 있도록 보관
+    // 복호화 결과처럼 평문이 담긴 파일은 wipe_outputs 를 true 로 주어 덮어쓴 뒤 삭제하도록 함
+    pub fn cancel(mut self, not_started: Vec<String>, wipe_outputs: bool) {
+        self.entries.extend(not_started.iter().cloned().map(ReportEntry::not_started));
+        let event = JobEvent::Cancelled {
+            job_id: self.job_id.clone(),
+            succeeded: std::mem::take(&mut self.succeeded),
+            failed: std::mem::take(&mut self.failed),
+            not_started,
+            output_count: self.outputs.len(),
+        };
+        self.send(event);
+        if !self.outputs.is_empty() {
+            let outputs = std::mem::take(&mut self.outputs);
+            self.registry.cancelled.lock().unwrap().insert(self.job_id.clone(), (outputs, wipe_outputs));
+        }
+        self.reason.get_or_insert_with(|| "Operation cancelled.".to_string());
+        self.store_report(JobStatus::Cancelled);
+    }
+
+    fn store_report(mut self, status: JobStatus) {
+        let count = |status: EntryStatus| self.entries.iter().filter(|e| e.status == status).count();
+        let recorded = self.audit.record(AuditEvent::Job {
+            job_id: self.job_id.clone(),
+            operation: self.operation.to_string(),
+            status,
+            total: self.entries.len(),
+            succeeded: count(EntryStatus::Succeeded),
+            failed: count(EntryStatus::Failed) + count(EntryStatus::RolledBack),
+            not_started: count(EntryStatus::NotStarted),
+        });
+        if let Err(e) = recorded {
+            self.problems.push(e);
+        }
+
+        let report = JobReport {
+            job_id: self.job_id,
+            operation: self.operation.to_string(),
+            status,
+            started_at: self.started_at,
+            finished_at: report::now_utc(),
+            duration_ms: self.started.elapsed().as_millis() as u64,
+            reason: self.reason,
+            problems: self.problems,
+            entries: self.entries,
+        };
+        let mut reports = self.registry.reports.lock().unwrap();
+        // 오래된 보고서부터 지워서 메모리에 계속 쌓이지 않도록 함
+        if reports.len() >= MAX_REPORTS {
+            reports.remove(0);
+        }
+        reports.push(report);
+    }
+}
+
+// 메모리에 보관할 최근 작업 보고서 수
+const MAX_REPORTS: usize = 50;
+
+/******************* 작업 기록 보관 ******************/
+// 취소된 작업이 만든 출력 파일은 프론트엔드에서 삭제하거나 그대로 두기로 할 때까지 작업 ID 별로 보관하고,
+// 끝난 작업의 보고서는 최근 MAX_REPORTS 개까지 보관
+#[derive(Default)]
+pub struct JobRegistry {
+    cancelled: Mutex<HashMap<String, (Vec<PathBuf>, bool)>>,
+    reports: Mutex<Vec<JobReport>>,
+}
+
+impl JobRegistry {
+    // 보관 중인 출력 파일 목록과 덮어쓰기 여부를 꺼냄
+    pub fn take_outputs(&self, job_id: &str) -> Option<(Vec<PathBuf>, bool)> {
+        self.cancelled.lock().unwrap().remove(job_id)
+    }
+
+    pub fn report(&self, job_id: &str) -> Option<JobReport> {
+        self.reports.lock().unwrap().iter().find(|r| r.job_id == job_id).cloned()
+    }
+}
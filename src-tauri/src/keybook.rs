@@ -0,0 +1,159 @@
+use aes_gcm::aead::{Aead, AeadCore, OsRng, Payload};
+use aes_gcm::{Aes256Gcm, Key, KeyInit, Nonce};
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use uuid::Uuid;
+
+use crate::envelope::Stanza;
+use crate::identity;
+use crate::progress;
+use crate::report;
+use crate::subkey::{self, Purpose};
+use crate::vault_file;
+
+const KEYBOOK_AAD: &[u8] = b"securevault keybook";
+const NONCE_LEN: usize = 12;
+
+// 이 vault 의 X25519 키 쌍. 다른 사람이 이 공개키로 암호화한 파일을 열 수 있음
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct Identity {
+    id: String,
+    name: String,
+    public_key: String,
+    secret_key: String,
+    created_at: String,
+}
+
+// 프론트엔드에 보여줄 키 정보 (비밀키 제외)
+#[derive(Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct IdentityInfo {
+    pub id: String,
+    pub name: String,
+    pub public_key: String,
+    pub created_at: String,
+}
+
+// 주소록에 등록한 동료의 공개키
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Contact {
+    pub id: String,
+    pub name: String,
+    pub public_key: String,
+    pub added_at: String,
+}
+
+/******************* 내 키 쌍과 동료 공개키 주소록 (vault 파일 옆의 <이름>.keys) ******************/
+// 비밀키가 들어 있으므로 파일 전체를 마스터 키에서 유도한 키로 암호화해서 저장함
+// [논스(12)] + [암호화된 JSON]
+#[derive(Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct KeyBook {
+    identities: Vec<Identity>,
+    contacts: Vec<Contact>,
+}
+
+impl KeyBook {
+    // 파일이 없으면 빈 목록
+    pub fn load(vault_path: &Path, vault_key: &Key<Aes256Gcm>) -> Result<Self, String> {
+        let path = keybook_path(vault_path);
+        if !path.exists() {
+            return Ok(KeyBook::default());
+        }
+        let data = fs::read(&path).map_err(|e| e.to_string())?;
+        if data.len() < NONCE_LEN { return Err("Key list is damaged".into()); }
+        let cipher = Aes256Gcm::new(&subkey::derive(vault_key, Purpose::KeyBook));
+        let payload = Payload { msg: &data[NONCE_LEN..], aad: KEYBOOK_AAD };
+        let json = cipher.decrypt(Nonce::from_slice(&data[..NONCE_LEN]), payload).map_err(|_| "Key list is damaged".to_string())?;
+        serde_json::from_slice(&json).map_err(|e| format!("Key list is damaged: {}", e))
+    }
+
+    pub fn save(&self, vault_path: &Path, vault_key: &Key<Aes256Gcm>) -> Result<(), String> {
+        let json = serde_json::to_vec(self).map_err(|e| e.to_string())?;
+        let cipher = Aes256Gcm::new(&subkey::derive(vault_key, Purpose::KeyBook));
+        let nonce = Aes256Gcm::generate_nonce(&mut OsRng);
+        let encrypted = cipher.encrypt(&nonce, Payload { msg: &json, aad: KEYBOOK_AAD }).map_err(|e| e.to_string())?;
+        let mut data = nonce.to_vec();
+        data.extend_from_slice(&encrypted);
+        progress::write_with_progress(&keybook_path(vault_path), &data, &mut |_| {})
+    }
+
+    pub fn identities(&self) -> Vec<IdentityInfo> {
+        self.identities.iter().map(Identity::info).collect()
+    }
+
+    pub fn contacts(&self) -> &[Contact] {
+        &self.contacts
+    }
+
+    pub fn create_identity(&mut self, name: &str) -> IdentityInfo {
+        let (secret_key, public_key) = identity::generate();
+        let created = Identity {
+            id: Uuid::new_v4().to_string(),
+            name: name.trim().to_string(),
+            public_key,
+            secret_key,
+            created_at: report::now_utc(),
+        };
+        let info = created.info();
+        self.identities.push(created);
+        info
+    }
+
+    // 지운 키 쌍으로만 열 수 있던 파일은 더 이상 복호화할 수 없음
+    pub fn remove_identity(&mut self, id: &str) -> Result<IdentityInfo, String> {
+        let index = self.identities.iter().position(|i| i.id == id).ok_or("Identity not found")?;
+        Ok(self.identities.remove(index).info())
+    }
+
+    // 이미 등록된 공개키면 이름만 바꿈
+    pub fn add_contact(&mut self, name: &str, public_key: &str) -> Result<Contact, String> {
+        let public_key = identity::encode_public_key(&identity::parse_public_key(public_key)?);
+        let name = name.trim().to_string();
+        if name.is_empty() { return Err("Name is required".into()); }
+        if let Some(existing) = self.contacts.iter_mut().find(|c| c.public_key == public_key) {
+            existing.name = name;
+            return Ok(existing.clone());
+        }
+        let contact = Contact { id: Uuid::new_v4().to_string(), name, public_key, added_at: report::now_utc() };
+        self.contacts.push(contact.clone());
+        Ok(contact)
+    }
+
+    pub fn remove_contact(&mut self, id: &str) -> Result<Contact, String> {
+        let index = self.contacts.iter().position(|c| c.id == id).ok_or("Contact not found")?;
+        Ok(self.contacts.remove(index))
+    }
+
+    // age 파일 복호화용 (AGE-SECRET-KEY-1... 형식)
+    pub fn secret_keys(&self) -> Vec<&str> {
+        self.identities.iter().map(|i| i.secret_key.as_str()).collect()
+    }
+
+    /******************* 내 키 쌍 중 하나로 파일 키를 복호화 ******************/
+    // 헤더에는 수신자가 누구인지 적지 않으므로 모든 키 쌍으로 차례로 시도
+    pub fn unwrap_file_key(&self, stanzas: &[Stanza]) -> Option<Vec<u8>> {
+        self.identities
+            .iter()
+            .filter_map(|i| identity::parse_secret_key(&i.secret_key).ok())
+            .find_map(|secret| stanzas.iter().find_map(|stanza| identity::unwrap_file_key(stanza, &secret)))
+    }
+}
+
+impl Identity {
+    fn info(&self) -> IdentityInfo {
+        IdentityInfo {
+            id: self.id.clone(),
+            name: self.name.clone(),
+            public_key: self.public_key.clone(),
+            created_at: self.created_at.clone(),
+        }
+    }
+}
+
+pub fn keybook_path(vault_path: &Path) -> PathBuf {
+    vault_file::side_path(vault_path, "keys")
+}
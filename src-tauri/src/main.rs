@@ -1,30 +1,67 @@
 // Prevents additional console window on Windows in release, DO NOT REMOVE!!
 #![cfg_attr(not(debug_assertions), windows_subsystem = "windows")]
 
-use tauri::{Emitter, Manager, State};
-use aes_gcm::aead::{Aead, OsRng};
-use aes_gcm::{Aes256Gcm, Key, KeyInit, Nonce, AeadCore};
-use argon2::{
-    self,
-    password_hash::{PasswordHasher, SaltString},
-    Argon2, Params, Version,
-};
+use tauri::ipc::Channel;
+use tauri::{Manager, State};
+use aes_gcm::{Aes256Gcm, Key};
 use rand::RngCore;
 use serde::{Deserialize, Serialize};
 use std::env;
-use std::fs::{self, File, OpenOptions};
-use std::io::{Read, Write};
+use std::fs::{self, File};
+use std::io::Read;
 use std::path::{Path, PathBuf};
+use std::time::{Duration, Instant};
 
 use std::sync::atomic::{AtomicBool, Ordering};
 use std::sync::{Arc, Mutex};
-use walkdir::WalkDir; 
 use uuid::Uuid;
 
+mod age_file;
+mod audit;
+mod cipher;
+mod data_key;
+mod envelope;
+mod free_space;
+mod identity;
+mod job;
+mod keybook;
+mod mirror;
+mod passphrase;
+mod progress;
+mod report;
+mod rotation;
+mod scan;
+mod settings;
+mod staging;
+mod storage;
+mod subkey;
+mod throttle;
+mod vault_file;
+mod vaults;
+mod wipe;
+use audit::{AuditEvent, AuditLog};
+use cipher::Cipher;
+use envelope::{FileHeader, Stanza};
+use passphrase::{KeyCache, PasswordKey};
+use job::{JobEvent, JobRegistry, JobReporter};
+use keybook::{Contact, IdentityInfo, KeyBook};
+use mirror::Manifest;
+use report::ReportFormat;
+use rotation::{KeyRing, RotationStatus};
+use staging::Staging;
+use scan::{ScanBatch, ScanEntry, ScanItem, ScanOptions, ScanResult};
+use settings::AppSettings;
+use storage::{StorageInfo, StorageProbe, WipeConfidence};
+use throttle::{Throttle, UnlockStatus, UnlockSummary};
+use vaults::{VaultInfo, VaultRegistry};
+use wipe::{TargetKind, WipeOptions};
+
 // 마스터 키를 메모리에 안전하게 보관할 구조체 정의
 // Mutex를 사용하여 여러 스레드에서 동시에 접근해도 안전하도록 처리
 pub struct Vault {
     key: Mutex<Option<Key<Aes256Gcm>>>,
+    // 마스터 키를 교체한 뒤 아직 폐기하지 않은 이전 키 (복호화와 새 키로 옮길 때만 사용)
+    previous_keys: Mutex<Vec<Key<Aes256Gcm>>>,
 }
 
 // 취소 상태를 안전하게 공유하기 위한 구조체
@@ -32,34 +69,56 @@ pub struct OperationState {
     is_cancelled: Arc<AtomicBool>,
 }
 
-// 프론트엔드로 보낼 진행 상황 이벤트 데이터 구조
-#[derive(Clone, serde::Serialize)]
-struct ProgressPayload {
-    status: String,
-    current_file_path: String,
-    number_of_files: usize,
-    current_file_number: usize,
-    total_progress: f64, // 전체 진행률 (0.0 ~ 1.0)
-    total_files: Option<Vec<String>>,
-    suceeded_files: Option<Vec<EachFile>>,
-    failed_files: Option<Vec<EachFile>>
-}
-
 // 파일 헤더에 저장될 메타데이터 구조체
+// 폴더 미러의 출력은 원본 폴더 기준 상대 경로('/' 구분)를 저장함
 #[derive(Serialize, Deserialize)]
 struct FileMetadata {
     original_filename: String,
 }
 
+// 복호화한 파일을 저장할 이름
+// 수신자 공개키나 비밀번호로 공유된 파일의 메타데이터는 보낸 사람이 정하므로, 경로 구분자나 . / .. 로 대상 폴더 밖을 가리키는 이름은 거부
+fn output_file_name(original_filename: &str) -> Result<&str, String> {
+    let is_plain_name = !matches!(original_filename, "" | "." | "..")
+        && !original_filename.contains(['/', '\\'])
+        && Path::new(original_filename).file_name().is_some_and(|name| name == original_filename);
+    if !is_plain_name {
+        return Err(format!("Invalid file: unsafe original file name {:?}", original_filename));
+    }
+    Ok(original_filename)
+}
+
+// 폴더 미러 출력처럼 '/' 로 구분된 상대 경로면 각 부분을 output_file_name 과 같은 규칙으로 확인해서 대상 폴더 아래의 경로로 만듦
+fn output_relative_path(original_filename: &str) -> Result<PathBuf, String> {
+    original_filename.split('/').map(output_file_name).collect::<Result<PathBuf, String>>()
+        .map_err(|_| format!("Invalid file: unsafe original file name {:?}", original_filename))
+}
+
+// 암호화 결과 파일 형식
+#[derive(Clone, Copy, Default, PartialEq, Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+enum OutputFormat {
+    #[default]
+    SecureVault, // 원래 파일 이름을 함께 암호화한 <UUID>.enc
+    Age, // age 도구로 열 수 있는 <원래 이름>.age
+}
+
 // 작업 결과 요약 구조체
-#[derive(Clone, serde::Serialize)]
+#[derive(Clone, Default, serde::Serialize)]
 struct EachFile {
     path: String,
-    error: Option<String>
+    error: Option<String>,
+    output: Option<String>, // 암호화/복호화로 만들어진 파일 경로
+    error_code: Option<String>, // 보고서용 오류 분류 (NOT_FOUND, PERMISSION_DENIED 등)
+    size: u64,
+    duration_ms: u64,
+    scheme: Option<String>, // 보안 삭제 시 사용한 덮어쓰기 방식
+    note: Option<String>, // 링크/특수 파일 등 항목별 처리 내용
+    confidence: Option<WipeConfidence>, // 보안 삭제 결과를 신뢰할 수 있는 정도
 }
 
 /******************* 앱 설정 파일 경로를 가져오는 헬퍼 함수 ******************/
-fn get_vault_path(app: &tauri::AppHandle) -> Result<PathBuf, String> {
+fn get_config_dir(app: &tauri::AppHandle) -> Result<PathBuf, String> {
     let config_dir = app.path()
         .app_config_dir()
         .map_err(|e| e.to_string())?;
@@ -67,133 +126,344 @@ fn get_vault_path(app: &tauri::AppHandle) -> Result<PathBuf, String> {
     if !config_dir.exists() {
         fs::create_dir_all(&config_dir).map_err(|e| e.to_string())?;
     }
-    Ok(config_dir.join("vault.key"))
+    Ok(config_dir)
 }
 
-/******************* 앱 시작 시 vault.key 파일이 있는지 확인 ******************/
+// 지금 선택된 vault 파일 경로 (vaults.json 에 등록된 것 중 하나)
+fn get_vault_path(app: &tauri::AppHandle) -> Result<PathBuf, String> {
+    let registry = VaultRegistry::load(&get_config_dir(app)?)?;
+    let selected = registry.selected().ok_or("No vault selected")?;
+    Ok(PathBuf::from(&selected.path))
+}
+
+fn get_settings_path(app: &tauri::AppHandle) -> Result<PathBuf, String> {
+    Ok(get_config_dir(app)?.join("settings.json"))
+}
+
+/******************* 앱 시작 시 선택된 vault 파일이 있는지 확인 ******************/
 #[tauri::command]
 fn vault_exists(app: tauri::AppHandle) -> Result<bool, String> {
-    let vault_path = get_vault_path(&app)?;
-    Ok(vault_path.exists())
+    let registry = VaultRegistry::load(&get_config_dir(&app)?)?;
+    Ok(registry.selected().is_some_and(|v| Path::new(&v.path).is_file()))
 }
 
-// 최초 실행 시 마스터 키 생성 및 저장
+/******************* 등록된 vault 목록 ******************/
+#[tauri::command]
+fn list_vaults(app: tauri::AppHandle) -> Result<Vec<VaultInfo>, String> {
+    Ok(VaultRegistry::load(&get_config_dir(&app)?)?.list())
+}
 
+/******************* 다른 위치에 있는 기존 vault 를 목록에 추가하고 선택 ******************/
 #[tauri::command]
-fn create_vault(app: tauri::AppHandle, password: String, vault_state: State<Vault> ) -> Result<(), String> {
+fn open_vault(app: tauri::AppHandle, path: String, name: Option<String>, vault_state: State<Vault>, audit: State<AuditLog>) -> Result<(), String> {
+    let vault_path = PathBuf::from(&path);
+    vault_file::check_format(&fs::read(&vault_path).map_err(|e| e.to_string())?)?;
+
+    let mut registry = VaultRegistry::load(&get_config_dir(&app)?)?;
+    registry.add(name, &vault_path);
+    registry.save()?;
+    lock(&vault_state, &audit);
+    Ok(())
+}
+
+/******************* 사용할 vault 를 바꿈 (이전 vault 는 잠금) ******************/
+#[tauri::command]
+fn switch_vault(app: tauri::AppHandle, id: String, vault_state: State<Vault>, audit: State<AuditLog>) -> Result<(), String> {
+    let mut registry = VaultRegistry::load(&get_config_dir(&app)?)?;
+    registry.select(&id)?;
+    registry.save()?;
+    lock(&vault_state, &audit);
+    Ok(())
+}
+
+/******************* vault 를 목록에서만 지움 (파일은 삭제하지 않음) ******************/
+#[tauri::command]
+fn forget_vault(app: tauri::AppHandle, id: String, vault_state: State<Vault>, audit: State<AuditLog>) -> Result<(), String> {
+    let mut registry = VaultRegistry::load(&get_config_dir(&app)?)?;
+    let was_selected = registry.selected().is_some_and(|v| v.id == id);
+    registry.forget(&id)?;
+    registry.save()?;
+    if was_selected {
+        lock(&vault_state, &audit);
+    }
+    Ok(())
+}
+
+/******************* 마스터 키를 메모리에서 지우고 잠금 ******************/
+#[tauri::command]
+fn lock_vault(vault_state: State<Vault>, audit: State<AuditLog>) {
+    lock(&vault_state, &audit);
+}
+
+fn lock(vault_state: &Vault, audit: &AuditLog) {
+    *vault_state.key.lock().unwrap() = None;
+    vault_state.previous_keys.lock().unwrap().clear();
+    audit.close();
+}
+
+// 최초 실행 시 마스터 키 생성 및 저장
+// path 를 주면 그 위치에 새 vault 를 만들어 목록에 추가하고, 없으면 선택된 vault(처음에는 설정 폴더의 vault.key)에 만듦
+#[tauri::command]
+fn create_vault(
+    app: tauri::AppHandle,
+    password: String,
+    name: Option<String>,
+    path: Option<String>,
+    vault_state: State<Vault>,
+    audit: State<AuditLog>,
+) -> Result<(), String> {
+    let config_dir = get_config_dir(&app)?;
+    let mut registry = VaultRegistry::load(&config_dir)?;
+    let vault_path = match (path, registry.selected()) {
+        (Some(path), _) => PathBuf::from(path),
+        (None, Some(selected)) => PathBuf::from(&selected.path),
+        (None, None) => config_dir.join("vault.key"),
+    };
+    if vault_path.exists() {
+        return Err("A vault already exists at this location".into());
+    }
+    // 같은 위치에 있던 이전 vault 의 키 목록과 이전 키 목록은 새 마스터 키로 열 수 없음
+    for stale_path in [keybook::keybook_path(&vault_path), rotation::keyring_path(&vault_path)] {
+        if stale_path.exists() {
+            fs::remove_file(&stale_path).map_err(|e| e.to_string())?;
+        }
+    }
+
     // 1. 새로운 마스터 키 (Vault Key)를 무작위로 생성
     let mut vault_key_bytes = [0u8; 32];
     rand::rng().fill_bytes(&mut vault_key_bytes);
     let vault_key = Key::<Aes256Gcm>::from_slice(&vault_key_bytes);
 
-    // 2. 비밀번호를 해싱하여 마스터 키를 암호화할 키(KEK) 생성
-    let mut salt_bytes = [0u8; 16];
-    rand::rng().fill_bytes(&mut salt_bytes);
-    let salt = SaltString::encode_b64(&salt_bytes).map_err(|e| e.to_string())?;
-    let argon2 = Argon2::new(  
-        argon2::Algorithm::Argon2id,
-        Version::V0x13,
-        Params::new(15000, 2, 1, None).unwrap()
-    );
-    let kek_hash = argon2.hash_password(password.as_bytes(), &salt).map_err(|e| e.to_string())?;
-    let binding = kek_hash.hash.unwrap();    
-    let kek = Key::<Aes256Gcm>::from_slice(&binding.as_bytes()[..32]);
-    
-    // 3. 마스터 키(Vault Key)를 KEK로 암호화
-    let cipher = Aes256Gcm::new(kek);
-    let mut nonce_bytes = [0u8; 12];
-    rand::rng().fill_bytes(&mut nonce_bytes);
-    let nonce = Nonce::from_slice(&nonce_bytes);
-    let encrypted_vault_key = cipher.encrypt(nonce, vault_key.as_slice()).map_err(|e| e.to_string())?;
-
-    // 4. [솔트] + [논스] + [암호화된 마스터 키] 형태로 파일에 저장
-    let mut final_data = Vec::new();
-    final_data.extend_from_slice(&salt_bytes);
-    final_data.extend_from_slice(&nonce_bytes);
-    final_data.extend_from_slice(&encrypted_vault_key);
+    // 2. 비밀번호로 만든 KEK 로 마스터 키를 암호화하여 [솔트] + [논스] + [암호화된 마스터 키] 형태로 파일에 저장
+    let final_data = vault_file::wrap_vault_key(vault_key.as_slice(), &password)?;
+    fs::write(&vault_path, &final_data).map_err(|e| e.to_string())?;
+    Throttle::new(&vault_path, &final_data).reset()?;
+    registry.add(name, &vault_path);
+    registry.save()?;
 
-    let vault_path = get_vault_path(&app)?;
-    fs::write(vault_path, final_data).map_err(|e| e.to_string())?;
-
-    // 5. 생성된 마스터 키를 즉시 메모리(State)에 로드
+    // 3. 생성된 마스터 키를 즉시 메모리(State)에 로드
     *vault_state.key.lock().unwrap() = Some(*vault_key);
+    vault_state.previous_keys.lock().unwrap().clear();
+
+    // 4. 새 감사 로그를 시작
+    audit.create(&vault_path, vault_key)?;
+    audit.record(AuditEvent::VaultCreated)?;
     Ok(())
 }
 
 /******************* 사용자가 입력한 비밀번호로 vault 잠금 해제 및 마스터 키를 메모리에 로드 ******************/
 #[tauri::command]
-fn unlock_vault(app: tauri::AppHandle, password: String, vault_state: tauri::State<Vault>) -> Result<(), String> {
+fn unlock_vault(app: tauri::AppHandle, password: String, vault_state: tauri::State<Vault>, audit: State<AuditLog>) -> Result<UnlockSummary, String> {
     let vault_path = get_vault_path(&app)?;
-    let vault_data = fs::read(vault_path).map_err(|e| e.to_string())?;
-
-    // 1. 파일에서 솔트, 논스, 암호화된 마스터 키 분리
-    if vault_data.len() < 28 { return Err("Invalid vault file".into()); }
-    let salt_bytes = &vault_data[0..16];
-    let nonce_bytes = &vault_data[16..28];
-    let encrypted_vault_key = &vault_data[28..];
-    let salt = SaltString::encode_b64(salt_bytes).map_err(|e| e.to_string())?;
-    let nonce = Nonce::from_slice(nonce_bytes);
-
-    // 2. 비밀번호로 KEK 재생성
-    let argon2 = Argon2::new(  
-        argon2::Algorithm::Argon2id,
-        Version::V0x13,
-        Params::new(15000, 2, 1, None).unwrap()
-    );
-    let kek_hash = argon2.hash_password(password.as_bytes(), &salt).map_err(|e| e.to_string())?;
-    let binding = kek_hash.hash.unwrap();    
-    let kek = Key::<Aes256Gcm>::from_slice(&binding.as_bytes()[..32]);
-
-    // 3. 마스터 키 복호화 시도
-    let cipher = Aes256Gcm::new(kek);
-    let vault_key_bytes = cipher.decrypt(nonce, encrypted_vault_key)
-        .map_err(|_| "Unlock failed. Check password.".to_string())?;
+    let vault_data = fs::read(&vault_path).map_err(|e| e.to_string())?;
+
+    // 1. 파일의 솔트로 비밀번호에서 KEK 를 다시 만들어 마스터 키 복호화 시도 (연속 실패로 대기 중이면 거부)
+    let (vault_key_bytes, mut summary) = unwrap_vault_key_throttled(&app, &vault_path, &vault_data, &password, "Unlock failed. Check password.")?;
     let vault_key = Key::<Aes256Gcm>::from_slice(&vault_key_bytes);
 
-    // 4. 성공 시, 마스터 키를 Tauri 상태(State)에 저장
+    // 2. 성공 시, 마스터 키를 Tauri 상태(State)에 저장 (키 교체 중이면 이전 키도 함께)
+    //    이후 단계의 문제는 잠금 해제를 막지 않고 결과의 warnings 로 알려줌
+    let previous_keys = load_previous_keys(&vault_path, vault_key).unwrap_or_else(|e| {
+        summary.warnings.push(e);
+        Vec::new()
+    });
     *vault_state.key.lock().unwrap() = Some(*vault_key);
+    *vault_state.previous_keys.lock().unwrap() = previous_keys.clone();
+
+    // 3. 키 교체 도중 앱이 종료되었다면 키 목록과 감사 로그가 아직 이전 키로 되어 있을 수 있으므로 먼저 옮김
+    if let Err(e) = rekey_side_files(&vault_path, vault_key, &previous_keys, &audit) {
+        summary.warnings.push(format!("Failed to move the key list or audit log to the current key: {}", e));
+    }
+
+    // 4. 감사 로그를 열고 잠겨 있는 동안의 실패 기록과 함께 기록
+    audit.open(&vault_path, vault_key)?;
+    if let Err(e) = audit.record(AuditEvent::UnlockSucceeded) {
+        summary.warnings.push(e);
+    }
+
+    // 5. 지난 성공 이후의 실패 횟수를 알려줌
+    Ok(summary)
+}
+
+/******************* 비밀번호로 vault 파일의 마스터 키 꺼내기 ******************/
+// 잠금 해제, 비밀번호 변경, 키 교체, 헤더 가져오기 등 비밀번호를 확인하는 곳은 모두 이 함수를 거침
+// 연속 실패로 대기 중이면 확인하지 않고 거부하고, 틀리면 실패를 기록해 삭제 기준에 도달하면 그 파일을 지움
+// 맞으면 실패 기록을 초기화하고 지난 성공 이후의 실패 기록을 반환
+fn unwrap_vault_key_throttled(
+    app: &tauri::AppHandle,
+    vault_path: &Path,
+    vault_data: &[u8],
+    password: &str,
+    incorrect: &str,
+) -> Result<(Vec<u8>, UnlockSummary), String> {
+    let policy = AppSettings::load(&get_settings_path(app)?).unlock_policy;
+    let throttle = Throttle::new(vault_path, vault_data);
+    throttle.check(&policy)?;
+    let Some(vault_key) = vault_file::unwrap_vault_key(vault_data, password)? else {
+        let logged = audit::record_unlock_failure(vault_path);
+        if throttle.record_failure(&policy)? {
+            erase_vault(vault_path)?;
+            return Err("Too many failed attempts. The vault was erased.".into());
+        }
+        return Err(match logged {
+            Ok(()) => incorrect.into(),
+            Err(e) => format!("{} ({})", incorrect, e),
+        });
+    };
+    Ok((vault_key, throttle.record_success()))
+}
+
+/******************* 잠금 화면에 표시할 실패 횟수와 남은 대기 시간 ******************/
+#[tauri::command]
+fn get_unlock_status(app: tauri::AppHandle) -> Result<UnlockStatus, String> {
+    let vault_path = get_vault_path(&app)?;
+    let vault_data = fs::read(&vault_path).map_err(|e| e.to_string())?;
+    let policy = AppSettings::load(&get_settings_path(&app)?).unlock_policy;
+    Ok(Throttle::new(&vault_path, &vault_data).status(&policy))
+}
+
+/******************* 실패 횟수 제한에 도달했을 때 vault 파일을 덮어쓴 뒤 삭제 ******************/
+// 마스터 키가 사라지므로 암호화된 파일과 감사 로그는 더 이상 복호화할 수 없음
+fn erase_vault(vault_path: &Path) -> Result<(), String> {
+    let never_cancel = AtomicBool::new(false);
+    wipe::wipe_file(vault_path, &WipeOptions::default(), &never_cancel, &mut |_| {})?;
+    // 백업은 이전 비밀번호로 마스터 키를 꺼낼 수 있으므로 함께 덮어씀 (키 목록에는 X25519 비밀키가 들어 있음)
+    let mut secrets = vault_file::backup_paths(vault_path);
+    secrets.push(keybook::keybook_path(vault_path));
+    secrets.push(rotation::keyring_path(vault_path));
+    for backup in secrets.iter().filter(|p| p.is_file()) {
+        let _ = wipe::wipe_file(backup, &WipeOptions::default(), &never_cancel, &mut |_| {});
+    }
+    let (log_path, head_path, pending_path) = audit::side_paths(vault_path);
+    for path in [log_path, head_path, pending_path, throttle::throttle_path(vault_path)] {
+        if path.exists() {
+            let _ = fs::remove_file(path);
+        }
+    }
     Ok(())
 }
 
 /******************* 폴더 내 모든 파일 경로를 가져오기 ******************/
+// 옵션(글롭 패턴, 숨김 파일, 크기, 깊이, 링크 처리)에 맞는 파일 목록과 함께,
+// 건너뛴 항목, 읽을 수 없었던 항목, 포함했지만 알릴 점(하드 링크)이 있는 항목을 이유와 함께 반환
 #[tauri::command]
-fn get_files_in_dir_recursive(dir_path: String) -> Result<Vec<String>, String> {
-    let mut files = Vec::new();
-    for entry in WalkDir::new(dir_path).into_iter().filter_map(|e| e.ok()) {
-        if entry.file_type().is_file() {
-            files.push(entry.path().to_string_lossy().to_string());
+fn get_files_in_dir_recursive(dir_path: String, options: Option<ScanOptions>) -> Result<ScanResult, String> {
+    let options = options.unwrap_or_default();
+    let mut result = ScanResult::default();
+    scan::scan_dir(Path::new(&dir_path), &options, &mut |item| {
+        match item {
+            ScanItem::File { path, size, note } => {
+                if let Some(reason) = note {
+                    result.notes.push(ScanEntry { path: path.clone(), reason });
+                }
+                result.files.push(path);
+                result.total_bytes += size;
+            }
+            ScanItem::Skipped(entry) => result.skipped.push(entry),
+            ScanItem::Error(entry) => result.errors.push(entry),
         }
-    }
-    Ok(files)
+        true
+    })?;
+    Ok(result)
+}
+
+/******************* 폴더 탐색 작업 (중간 결과를 나눠서 전송) ******************/
+// 파일이 많은 폴더에서도 기다리지 않도록, 찾은 항목을 on_batch 채널로 묶어서 보내고
+// cancel_operation 으로 중단할 수 있음. 반환값에는 마지막 묶음과 누적 개수/크기가 담김
+#[tauri::command]
+async fn scan_folder(
+    dir_path: String,
+    options: Option<ScanOptions>,
+    on_batch: Channel<ScanBatch>,
+    op_state: State<'_, OperationState>,
+) -> Result<ScanBatch, String> {
+    // 한 번에 보낼 최대 항목 수와 최소 전송 간격
+    const BATCH_SIZE: usize = 1000;
+    const BATCH_INTERVAL: Duration = Duration::from_millis(200);
+
+    let options = options.unwrap_or_default();
+    op_state.is_cancelled.store(false, Ordering::SeqCst);
+    let cancel_flag = op_state.is_cancelled.clone();
+
+    let mut batch = ScanBatch::default();
+    let mut last_emit = Instant::now();
+    scan::scan_dir(Path::new(&dir_path), &options, &mut |item| {
+        if cancel_flag.load(Ordering::SeqCst) { return false; }
+        batch.push(item);
+        if batch.pending_len() >= BATCH_SIZE || last_emit.elapsed() >= BATCH_INTERVAL {
+            // 창이 닫혀서 보낼 수 없으면 탐색을 계속할 필요가 없음
+            if on_batch.send(batch.take("SCANNING")).is_err() { return false; }
+            last_emit = Instant::now();
+        }
+        true
+    })?;
+
+    // 마지막 묶음은 채널 메시지보다 늦게 도착할 수 있으므로 반환값으로 전달
+    let status = if cancel_flag.load(Ordering::SeqCst) { "CANCELLED" } else { "DONE" };
+    Ok(batch.take(status))
 }
 
 /******************* 암호화 함수 ******************/
 #[tauri::command]
+#[allow(clippy::too_many_arguments)] // 상태(State)는 Tauri 가 주입하므로 구조체로 묶을 수 없음
 async fn encrypt_files(
     app: tauri::AppHandle,
     vault: State<'_, Vault>,
     files: Vec<String>,
     destination_dir: String,
+    transactional: Option<bool>,
+    recipients: Option<Vec<String>>,
+    format: Option<OutputFormat>,
+    password: Option<String>,
+    cipher: Option<Cipher>,
+    on_event: Channel<JobEvent>,
     op_state: State<'_, OperationState>,
+    jobs: State<'_, JobRegistry>,
+    audit: State<'_, AuditLog>,
 ) -> Result<(), String> {
     let vault_key = vault.key.lock().unwrap().clone().ok_or("Vault is locked")?;
+    // 알고리즘을 지정하지 않으면 설정의 기본값을 사용
+    let cipher = match cipher {
+        Some(cipher) => cipher,
+        None => AppSettings::load(&get_settings_path(&app)?).default_cipher,
+    };
+    // 본문은 파일마다 만든 키로 암호화하고, 그 키는 수신자(공개키)나 비밀번호를 지정하면 수신자마다/비밀번호로,
+    // 아니면 vault 키로 암호화해 헤더에 넣음. age 형식은 수신자 또는 비밀번호 중 하나로 암호화
+    let format = format.unwrap_or_default();
+    let recipients = recipients.unwrap_or_default();
+    let recipient_keys = recipients.iter().map(|r| identity::parse_public_key(r)).collect::<Result<Vec<_>, _>>()?;
+    if format == OutputFormat::Age && recipients.is_empty() == password.is_none() {
+        return Err("age output needs either recipients or a passphrase".into());
+    }
+    let password_key = match (format, password.as_deref()) {
+        (OutputFormat::SecureVault, Some(password)) => Some(PasswordKey::new(password)?),
+        _ => None,
+    };
     op_state.is_cancelled.store(false, Ordering::SeqCst);
     let cancel_flag = op_state.is_cancelled.clone();
     
-    let number_of_files = files.len();
-    let total_files = files.clone();
-
     let total_size: u64 = files.iter().map(|p| fs::metadata(p).map(|m| m.len()).unwrap_or(0)).sum();
-    let mut total_bytes_processed: u64 = 0;
-
-    let mut suceeded_files = Vec::new();
-    let mut failed_files = Vec::new();
+    let mut reporter = JobReporter::start(on_event, &jobs, &audit, "ENCRYPT", files.len(), total_size);
+    // 트랜잭션 모드: 출력은 임시 폴더에 모아 두었다가 전부 성공했을 때만 제자리로 옮김
+    let mut staging = None;
+    if transactional.unwrap_or(false) {
+        match Staging::create(Path::new(&destination_dir), reporter.job_id(), false) {
+            Ok(created) => staging = Some(created),
+            Err(e) => {
+                reporter.rolled_back(e, files.clone());
+                reporter.finish();
+                return Ok(());
+            }
+        }
+    }
+    let mut failure = None;
 
-    for (index, file_path) in files.iter().enumerate() {
+    for file_path in files.iter() {
         if cancel_flag.load(Ordering::SeqCst) { break; }
 
         let file_size = fs::metadata(file_path).map(|m| m.len()).unwrap_or(0);
-        let result: Result<(), String> = (|| {
+        reporter.file_started(file_path, file_size);
+        let result: Result<PathBuf, String> = (|| {
             let source_path = Path::new(file_path);
             
             let metadata = FileMetadata {
@@ -201,146 +471,279 @@ async fn encrypt_files(
             };
             let metadata_bytes = serde_json::to_vec(&metadata).map_err(|e| e.to_string())?;
 
-            let cipher = Aes256Gcm::new(&vault_key);
-            let metadata_nonce = Aes256Gcm::generate_nonce(&mut OsRng); // 이제 이 함수를 찾을 수 있습니다.
-            let encrypted_metadata = cipher.encrypt(&metadata_nonce, metadata_bytes.as_ref()).map_err(|e| e.to_string())?;
+            // 읽기와 쓰기에 각각 파일 크기의 절반씩 진행률을 배분
+            let half_size = file_size / 2;
+            let original_data = progress::read_with_progress(source_path, &mut |read_bytes| {
+                reporter.file_progress(progress::scale(read_bytes, file_size, half_size));
+            })?;
 
-            let original_data = fs::read(&file_path).map_err(|e| e.to_string())?;
-            let content_nonce = Aes256Gcm::generate_nonce(&mut OsRng); // 여기도 마찬가지
-            let encrypted_content = cipher.encrypt(&content_nonce, original_data.as_ref()).map_err(|e| e.to_string())?;
-            
-            let mut final_data = Vec::new();
-            final_data.extend_from_slice(metadata_nonce.as_slice());
-            final_data.extend_from_slice(&(encrypted_metadata.len() as u16).to_be_bytes());
-            final_data.extend_from_slice(&encrypted_metadata);
-            final_data.extend_from_slice(content_nonce.as_slice());
-            final_data.extend_from_slice(&encrypted_content);
+            let final_data = if format == OutputFormat::Age {
+                age_file::encrypt(&original_data, &recipients, password.as_deref())?
+            } else {
+                // 파일마다 만든 데이터 키로 본문을 암호화하고, 데이터 키는 vault 키와 수신자/비밀번호로 각각 암호화해 헤더에 넣음
+                // 공유한 파일도 보낸 사람의 vault 에서 다시 열 수 있도록 vault 키 항목은 항상 넣음
+                let file_key = data_key::generate();
+                let mut recipients = vec![data_key::wrap(&file_key, &vault_key)?];
+                for recipient in &recipient_keys {
+                    recipients.push(identity::wrap_file_key(file_key.as_slice(), recipient)?);
+                }
+                if let Some(password_key) = &password_key {
+                    recipients.push(password_key.wrap_file_key(file_key.as_slice())?);
+                }
+                let header = FileHeader::new(cipher, recipients);
+                let mut data = envelope::write_header(&header)?;
+                data.extend_from_slice(&envelope::seal_body(&header, &file_key, &metadata_bytes, &original_data)?);
+                data
+            };
             
-            let random_filename = format!("{}.enc", Uuid::new_v4().to_string());
-            let dest_path = Path::new(&destination_dir).join(random_filename);
-            fs::write(dest_path, final_data).map_err(|e| e.to_string())?;
+            let dest_name = match format {
+                OutputFormat::SecureVault => format!("{}.enc", Uuid::new_v4().to_string()),
+                OutputFormat::Age => age_file::encrypted_name(source_path),
+            };
+            let dest_path = Path::new(&destination_dir).join(dest_name);
+            // 트랜잭션 모드는 commit 할 때 같은 규칙으로 확인함
+            if staging.is_none() { staging::ensure_absent(&dest_path)?; }
+            let write_path = staging.as_mut().map_or_else(|| dest_path.clone(), |s| s.stage(&dest_path));
+            let output_size = final_data.len() as u64;
+            progress::write_with_progress(&write_path, &final_data, &mut |written_bytes| {
+                reporter.file_progress(half_size + progress::scale(written_bytes, output_size, file_size - half_size));
+            })?;
 
-            Ok(())
+            Ok(dest_path)
         })();
 
-        match result {
-            Ok(_) => {
-                suceeded_files.push(EachFile { path: file_path.clone(), error: None });
-                total_bytes_processed += file_size;
+        let file = match result {
+            Ok(dest_path) => EachFile {
+                path: file_path.clone(),
+                output: Some(dest_path.to_string_lossy().to_string()),
+                ..Default::default()
             },
-            Err(e) => failed_files.push(EachFile { path: file_path.clone(), error: Some(e) }),
+            Err(e) => EachFile { path: file_path.clone(), error: Some(e), ..Default::default() },
+        };
+        // 트랜잭션 모드에서는 하나라도 실패하면 나머지를 처리할 필요가 없음
+        if staging.is_some() {
+            failure = file.error.as_ref().map(|e| format!("{}: {}", file_path, e));
         }
-
-        let total_progress = if total_size > 0 { total_bytes_processed as f64 / total_size as f64 } else { 0.0 };
-        app.emit("PROGRESS_EVENT", ProgressPayload {
-            status: "PROCESSING".to_string(),
-            current_file_path: file_path.clone(),
-            number_of_files,
-            current_file_number: index + 1,
-            total_progress,//: (index + 1) as f64 / number_of_files as f64,
-            total_files: None,
-            suceeded_files: None,
-            failed_files: None,
-        }).unwrap();
+        reporter.file_finished(file, file_size);
+        if failure.is_some() { break; }
     }
       
-    if !cancel_flag.load(Ordering::SeqCst) {
-        app.emit("PROGRESS_EVENT", ProgressPayload { 
-            status: "DONE".to_string(),
-            current_file_path: "Done".to_string(), 
-            number_of_files,
-            current_file_number: number_of_files,
-            total_progress: 1.0,
-            total_files: Some(total_files),
-            suceeded_files: Some(suceeded_files),
-            failed_files: Some(failed_files)
-        }).unwrap();
-    }
-    
+    finish_job(reporter, staging, failure, &files, cancel_flag.load(Ordering::SeqCst), false);
     Ok(())
 }
 
 /******************* 복호화 함수 ******************/
 #[tauri::command]
+#[allow(clippy::too_many_arguments)] // 상태(State)는 Tauri 가 주입하므로 구조체로 묶을 수 없음
 async fn decrypt_files(
     app: tauri::AppHandle,
     vault: State<'_, Vault>,
     files: Vec<String>,
     destination_dir: String,
+    transactional: Option<bool>,
+    password: Option<String>,
+    on_event: Channel<JobEvent>,
     op_state: State<'_, OperationState>,
+    jobs: State<'_, JobRegistry>,
+    audit: State<'_, AuditLog>,
 ) -> Result<(), String> {
     let vault_key = vault.key.lock().unwrap().clone().ok_or("Vault is locked")?;
+    // 키 교체 중이면 아직 옮기지 않은 파일을 이전 키로도 열 수 있음
+    let master_keys: Vec<Key<Aes256Gcm>> = std::iter::once(vault_key).chain(vault.previous_keys.lock().unwrap().iter().copied()).collect();
     op_state.is_cancelled.store(false, Ordering::SeqCst);
     let cancel_flag = op_state.is_cancelled.clone();
     
-    let number_of_files = files.len();
-    let total_files = files.clone();
-
     let total_size: u64 = files.iter().map(|p| fs::metadata(p).map(|m| m.len()).unwrap_or(0)).sum();
-    let mut total_bytes_processed: u64 = 0;
-
-    let mut suceeded_files = Vec::new();
-    let mut failed_files = Vec::new();
+    // 수신자 형식의 파일은 이 vault 의 X25519 키 쌍으로 파일 키를 꺼냄
+    // 키 목록을 읽을 수 없어도 vault 키로 암호화된 파일은 복호화할 수 있도록 오류는 필요할 때 보고
+    let keybook = get_vault_path(&app).and_then(|vault_path| KeyBook::load(&vault_path, &vault_key));
+    let mut password_keys = KeyCache::new();
+    let mut reporter = JobReporter::start(on_event, &jobs, &audit, "DECRYPT", files.len(), total_size);
+    // 트랜잭션 모드: 출력은 임시 폴더에 모아 두었다가 전부 성공했을 때만 제자리로 옮김
+    let mut staging = None;
+    if transactional.unwrap_or(false) {
+        match Staging::create(Path::new(&destination_dir), reporter.job_id(), true) {
+            Ok(created) => staging = Some(created),
+            Err(e) => {
+                reporter.rolled_back(e, files.clone());
+                reporter.finish();
+                return Ok(());
+            }
+        }
+    }
+    let mut failure = None;
 
-    for (index, file_path) in files.iter().enumerate() {
+    for file_path in files.iter() {
         if cancel_flag.load(Ordering::SeqCst) { break; }
 
         let file_size = fs::metadata(file_path).map(|m| m.len()).unwrap_or(0);
-        let result: Result<(), String> = (|| {
-            let encrypted_file_data = fs::read(file_path).map_err(|e| e.to_string())?;
-            if encrypted_file_data.len() < 14 { return Err("Invalid file: too short for header".into()); }
-            let metadata_nonce = Nonce::from_slice(&encrypted_file_data[0..12]);
-            let encrypted_metadata_len = u16::from_be_bytes(encrypted_file_data[12..14].try_into().unwrap()) as usize;
-            let metadata_end = 14 + encrypted_metadata_len;
-            if encrypted_file_data.len() < metadata_end + 12 { return Err("Invalid file: metadata length mismatch".into()); }
-            let encrypted_metadata = &encrypted_file_data[14..metadata_end];
-            let content_nonce = Nonce::from_slice(&encrypted_file_data[metadata_end..metadata_end + 12]);
-            let encrypted_content = &encrypted_file_data[metadata_end + 12..];
-            let cipher = Aes256Gcm::new(&vault_key);
-            let metadata_bytes = cipher.decrypt(metadata_nonce, encrypted_metadata.as_ref()).map_err(|_| "Decryption failed: metadata corrupt".to_string())?;
+        reporter.file_started(file_path, file_size);
+        let result: Result<PathBuf, String> = (|| {
+            // 읽기와 쓰기에 각각 파일 크기의 절반씩 진행률을 배분
+            let half_size = file_size / 2;
+            let encrypted_file_data = progress::read_with_progress(Path::new(file_path), &mut |read_bytes| {
+                reporter.file_progress(progress::scale(read_bytes, file_size, half_size));
+            })?;
+            // age 파일은 이 vault 의 X25519 키 쌍이나 비밀번호로 복호화하고, 이름은 .age 를 뗀 원래 이름을 사용
+            if age_file::is_age(&encrypted_file_data) {
+                // 비밀번호로 암호화된 age 파일은 X25519 키가 필요 없으므로, 키 목록을 읽지 못한 오류는 수신자 항목이 있는 파일에서만 보고
+                let secret_keys = match &keybook {
+                    Ok(keybook) => keybook.secret_keys(),
+                    Err(_) if age_file::needs_passphrase(encrypted_file_data.as_slice()) => Vec::new(),
+                    Err(e) => return Err(e.clone()),
+                };
+                let decrypted_content = age_file::decrypt(&encrypted_file_data, &secret_keys, password.as_deref())?;
+                let dest_path = Path::new(&destination_dir).join(age_file::decrypted_name(Path::new(file_path)));
+                if staging.is_none() { staging::ensure_absent(&dest_path)?; }
+                let write_path = staging.as_mut().map_or_else(|| dest_path.clone(), |s| s.stage(&dest_path));
+                let output_size = decrypted_content.len() as u64;
+                progress::write_with_progress(&write_path, &decrypted_content, &mut |written_bytes| {
+                    reporter.file_progress(half_size + progress::scale(written_bytes, output_size, file_size - half_size));
+                })?;
+                return Ok(dest_path);
+            }
+            let (metadata_bytes, decrypted_content) = match envelope::read_header(&encrypted_file_data) {
+                Some((header, body)) => {
+                    header.validate()?;
+                    let file_key = open_file_key(&header, &master_keys, &keybook, password.as_deref(), &mut password_keys)?;
+                    envelope::open_body(&header, Key::<Aes256Gcm>::from_slice(&file_key), body)?
+                }
+                None => open_with_master_keys(&master_keys, &encrypted_file_data)?,
+            };
             let metadata: FileMetadata = serde_json::from_slice(&metadata_bytes).map_err(|e| e.to_string())?;
-            let decrypted_content = cipher.decrypt(content_nonce, encrypted_content.as_ref()).map_err(|_| "Decryption failed: content corrupt".to_string())?;
-            let dest_path = Path::new(&destination_dir).join(metadata.original_filename);
-            fs::write(dest_path, decrypted_content).map_err(|e| e.to_string())?;
-            Ok(())
+            let dest_path = Path::new(&destination_dir).join(output_relative_path(&metadata.original_filename)?);
+            // 트랜잭션 모드는 commit 할 때 같은 규칙으로 확인하고 하위 폴더를 만듦
+            if staging.is_none() {
+                staging::ensure_absent(&dest_path)?;
+                staging::create_parent_dirs(Path::new(&destination_dir), &dest_path)?;
+            }
+            let write_path = staging.as_mut().map_or_else(|| dest_path.clone(), |s| s.stage(&dest_path));
+            let output_size = decrypted_content.len() as u64;
+            progress::write_with_progress(&write_path, &decrypted_content, &mut |written_bytes| {
+                reporter.file_progress(half_size + progress::scale(written_bytes, output_size, file_size - half_size));
+            })?;
+            Ok(dest_path)
         })();
         
-        match result {
-            Ok(_) => {
-                suceeded_files.push(EachFile { path: file_path.clone(), error: None });
-                total_bytes_processed += file_size;
+        let file = match result {
+            Ok(dest_path) => EachFile {
+                path: file_path.clone(),
+                output: Some(dest_path.to_string_lossy().to_string()),
+                ..Default::default()
             },
-            Err(e) => failed_files.push(EachFile { path: file_path.clone(), error: Some(e) }),
+            Err(e) => EachFile { path: file_path.clone(), error: Some(e), ..Default::default() },
+        };
+        // 트랜잭션 모드에서는 하나라도 실패하면 나머지를 처리할 필요가 없음
+        if staging.is_some() {
+            failure = file.error.as_ref().map(|e| format!("{}: {}", file_path, e));
         }
-
-        let total_progress = if total_size > 0 { total_bytes_processed as f64 / total_size as f64 } else { 0.0 };
-        app.emit("PROGRESS_EVENT", ProgressPayload {
-            status: "PROCESSING".to_string(),
-            current_file_path: file_path.clone(),
-            number_of_files,
-            current_file_number: index + 1,
-            total_progress,//: (index + 1) as f64 / number_of_files as f64,
-            total_files: None,
-            suceeded_files: None,
-            failed_files: None,
-        }).unwrap();
+        reporter.file_finished(file, file_size);
+        if failure.is_some() { break; }
     }
     
-    if !cancel_flag.load(Ordering::SeqCst) {
-        app.emit("PROGRESS_EVENT", ProgressPayload { 
-            status: "DONE".to_string(),
-            current_file_path: "Done".to_string(), 
-            number_of_files,
-            current_file_number: number_of_files,
-            total_progress: 1.0,
-            total_files: Some(total_files),
-            suceeded_files: Some(suceeded_files),
-            failed_files: Some(failed_files)
-        }).unwrap();
-    }
+    // 복호화된 파일은 평문이므로 지울 때 덮어쓰기 후 삭제
+    finish_job(reporter, staging, failure, &files, cancel_flag.load(Ordering::SeqCst), true);
     Ok(())
 }
 
+/******************* 헤더 없는 기존 형식(마스터 키로 바로 암호화된 본문) 복호화 ******************/
+// 현재 키로 먼저 시도하고 키 교체 중이면 이전 키로도 시도 (모두 실패하면 현재 키로 시도한 오류를 보고)
+fn open_with_master_keys(master_keys: &[Key<Aes256Gcm>], body: &[u8]) -> Result<(Vec<u8>, Vec<u8>), String> {
+    let mut first_error = None;
+    for key in master_keys {
+        match envelope::open_body(&FileHeader::default(), key, body) {
+            Ok(opened) => return Ok(opened),
+            Err(e) => {
+                first_error.get_or_insert(e);
+            }
+        }
+    }
+    Err(first_error.unwrap_or_else(|| "Vault is locked".to_string()))
+}
+
+/******************* 헤더에서 파일 키 꺼내기 ******************/
+// 마스터 키(현재, 이전), 내 X25519 키 쌍, 비밀번호 순서로 시도
+fn open_file_key(
+    header: &FileHeader,
+    master_keys: &[Key<Aes256Gcm>],
+    keybook: &Result<KeyBook, String>,
+    password: Option<&str>,
+    password_keys: &mut KeyCache,
+) -> Result<Vec<u8>, String> {
+    let from_master_key = header.recipients.iter().find_map(|stanza| master_keys.iter().find_map(|key| data_key::unwrap(stanza, key)));
+    if let Some(file_key) = from_master_key {
+        return Ok(file_key);
+    }
+    if let Some(file_key) = keybook.as_ref().ok().and_then(|keybook| keybook.unwrap_file_key(&header.recipients)) {
+        return Ok(file_key);
+    }
+    if header.has_password() {
+        let password = password.ok_or("Decryption failed: this file is protected with a password")?;
+        for stanza in &header.recipients {
+            if let Some(file_key) = passphrase::unwrap_file_key(stanza, password, password_keys)? {
+                return Ok(file_key);
+            }
+        }
+    }
+    // 키 목록을 읽지 못했다면 그 이유를 알려줌
+    keybook.as_ref().map_err(|e| e.clone())?;
+    if header.recipients.iter().any(|stanza| matches!(stanza, Stanza::Vault { .. })) {
+        return Err("Decryption failed: this file was encrypted with a different vault key".into());
+    }
+    Err("Decryption failed: no identity in this vault is a recipient of this file".into())
+}
+
+/******************* 복호화하기 전에 비밀번호를 물어봐야 하는지 확인 ******************/
+// 비밀번호로 암호화된 age 파일이나 비밀번호 항목이 있는 파일이 하나라도 있으면 true (파일 앞부분의 헤더만 읽음)
+#[tauri::command]
+fn files_need_password(files: Vec<String>) -> bool {
+    files.iter().any(|path| {
+        let mut prefix = Vec::new();
+        let read = File::open(path).and_then(|file| file.take(HEADER_PEEK_LEN).read_to_end(&mut prefix));
+        read.is_ok()
+            && (age_file::needs_passphrase(prefix.as_slice())
+                || envelope::read_header(&prefix).is_some_and(|(header, _)| header.has_password()))
+    })
+}
+
+// 헤더를 확인하기 위해 읽는 최대 크기 (수신자가 수백 명이어도 충분함)
+const HEADER_PEEK_LEN: u64 = 256 * 1024;
+
+/******************* 암호화/복호화 작업 마무리 ******************/
+// 트랜잭션 모드면 모두 성공했을 때만 결과를 반영하고, 실패/취소 시에는 모두 되돌린 뒤 이유를 보냄
+// 트랜잭션 모드가 아닌 작업이 취소되면 이미 만들어진 출력 파일은 remove_job_outputs 로 지울 수 있도록 보관
+fn finish_job(
+    mut reporter: JobReporter,
+    staging: Option<Staging>,
+    failure: Option<String>,
+    files: &[String],
+    cancelled: bool,
+    wipe_outputs: bool,
+) {
+    if let Some(staging) = staging {
+        let reason = if cancelled { Some("Operation cancelled.".to_string()) } else { failure };
+        // commit 은 옮기는 중에 실패하면 스스로 되돌림
+        let result = match reason {
+            Some(reason) => {
+                staging.rollback();
+                Err(reason)
+            }
+            None => staging.commit(),
+        };
+        if let Err(reason) = result {
+            let not_started = if cancelled { Vec::new() } else { files[reporter.files_started()..].to_vec() };
+            reporter.rolled_back(reason, not_started);
+        }
+    }
+
+    if cancelled {
+        let not_started = files[reporter.files_started()..].to_vec();
+        reporter.cancel(not_started, wipe_outputs);
+    } else {
+        reporter.finish();
+    }
+}
+
 /******************* 암호화/복호화 취소 ******************/
 #[tauri::command]
 fn cancel_operation(op_state: State<OperationState>) -> Result<(), String> {
@@ -348,168 +751,784 @@ fn cancel_operation(op_state: State<OperationState>) -> Result<(), String> {
     Ok(())
 }
 
+/******************* 취소된 작업이 이미 만든 출력 파일 삭제 ******************/
+// 취소 요약(cancelled)의 job_id 로 요청하며, 파일별 삭제 결과를 반환
+#[tauri::command]
+async fn remove_job_outputs(job_id: String, jobs: State<'_, JobRegistry>) -> Result<Vec<EachFile>, String> {
+    let (outputs, wipe_outputs) = jobs.take_outputs(&job_id).ok_or("No outputs to remove for this job")?;
+    let never_cancel = AtomicBool::new(false);
+
+    let results = outputs.into_iter()
+        .map(|output| {
+            let result = if wipe_outputs {
+                wipe::wipe_file(&output, &WipeOptions::default(), &never_cancel, &mut |_| {})
+            } else {
+                fs::remove_file(&output).map_err(|e| e.to_string())
+            };
+            EachFile { path: output.to_string_lossy().to_string(), error: result.err(), ..Default::default() }
+        })
+        .collect();
+    Ok(results)
+}
+
+/******************* 취소된 작업의 출력 파일을 그대로 두기로 한 경우 ******************/
+#[tauri::command]
+fn keep_job_outputs(job_id: String, jobs: State<JobRegistry>) {
+    jobs.take_outputs(&job_id);
+}
+
+/******************* 끝난 작업의 처리 기록을 JSON/CSV 파일로 저장 ******************/
+// 항목마다 입력/출력 경로, 크기, 걸린 시간, 상태, 오류 코드와 메시지를 기록 (최근 작업만 보관됨)
+#[tauri::command]
+fn export_job_report(job_id: String, format: ReportFormat, path: String, jobs: State<JobRegistry>) -> Result<(), String> {
+    let report = jobs.report(&job_id).ok_or("Report for this job is no longer available")?;
+    report.write(Path::new(&path), format)
+}
+
 /******************* 보안 삭제 함수 ******************/
+// 파일은 덮어쓴 뒤 이름을 지워서 삭제하고, 폴더가 주어지면 안의 항목을 모두 지운 뒤 폴더도 아래에서 위로 같은 방식으로 삭제
 #[tauri::command]
 async fn secure_delete_files(
-    app: tauri::AppHandle,
     files: Vec<String>,
+    options: Option<WipeOptions>,
+    on_event: Channel<JobEvent>,
     op_state: State<'_, OperationState>,
+    jobs: State<'_, JobRegistry>,
+    audit: State<'_, AuditLog>,
 ) -> Result<(), String> {
+    let options = options.unwrap_or_default();
+    let scheme_label = options.scheme.label();
     op_state.is_cancelled.store(false, Ordering::SeqCst);
     let cancel_flag = op_state.is_cancelled.clone();
-    
-    let number_of_files = files.len();
-    let total_files = files.clone();
 
-    let total_size: u64 = files.iter().map(|p| fs::metadata(p).map(|m| m.len()).unwrap_or(0)).sum();
-    let mut total_bytes_processed: u64 = 0;
+    let (targets, walk_errors) = wipe::collect_targets(&files);
+    let total_size: u64 = targets.iter().map(|t| t.size).sum();
+    let mut reporter = JobReporter::start(on_event, &jobs, &audit, "SECURE_DELETE", targets.len(), total_size);
+    for (path, error) in walk_errors {
+        reporter.file_failed_early(EachFile { path, error: Some(error), ..Default::default() });
+    }
 
-    let mut suceeded_files = Vec::new();
-    let mut failed_files = Vec::new();
+    let mut probe = StorageProbe::default();
 
-    for (index, file_path) in files.iter().enumerate() {
+    for target in targets.iter() {
         if cancel_flag.load(Ordering::SeqCst) { break; }
 
-        let file_size = fs::metadata(file_path).map(|m| m.len()).unwrap_or(0);
-        let result: Result<(), String> = (|| {
-            let path = Path::new(file_path);
-            let metadata = fs::metadata(path).map_err(|e| e.to_string())?;
-            if metadata.is_file() {
-                let file_size = metadata.len();
-                let mut file = OpenOptions::new().write(true).open(path).map_err(|e| e.to_string())?;
-                
-                const CHUNK_SIZE: usize = 1024 * 1024;
-                let mut buffer = vec![0u8; CHUNK_SIZE];
-                let mut written_bytes = 0u64;
-
-                while written_bytes < file_size {
-                    if cancel_flag.load(Ordering::SeqCst) {
-                        // 덮어쓰기 중단 시 파일 삭제하지 않음
-                        return Err("Operation cancelled.".to_string());
-                    }
-                    rand::rng().fill_bytes(&mut buffer);
-                    let bytes_to_write = std::cmp::min(file_size - written_bytes, CHUNK_SIZE as u64) as usize;
-                    file.write_all(&buffer[..bytes_to_write]).map_err(|e| e.to_string())?;
-                    written_bytes += bytes_to_write as u64;
-                }
-                file.sync_all().map_err(|e| e.to_string())?;
-                fs::remove_file(path).map_err(|e| e.to_string())?;
-            }
-            Ok(())
-        })();
+        let file_path = target.path.to_string_lossy().to_string();
+        reporter.file_started(&file_path, target.size);
+        let result = wipe::wipe_target(target, &options, &mut probe, &cancel_flag, &mut |wiped_bytes| {
+            reporter.file_progress(wiped_bytes);
+        });
+        let scheme = (target.kind == TargetKind::File).then(|| scheme_label.clone());
 
-        match result {
-            Ok(_) => {
-                suceeded_files.push(EachFile { path: file_path.clone(), error: None });
-                total_bytes_processed += file_size;
+        let file = match result {
+            Ok(outcome) => EachFile {
+                path: file_path,
+                error: None,
+                scheme,
+                note: outcome.note,
+                confidence: outcome.storage.map(|s| s.confidence),
+                ..Default::default()
             },
-            Err(e) => failed_files.push(EachFile { path: file_path.clone(), error: Some(e) }),
-        }
-
-        let total_progress = if total_size > 0 { total_bytes_processed as f64 / total_size as f64 } else { 0.0 };
-        app.emit("PROGRESS_EVENT", ProgressPayload {
-            status: "PROCESSING".to_string(),
-            current_file_path: file_path.clone(),
-            number_of_files,
-            current_file_number: index + 1,
-            total_progress,//: (index + 1) as f64 / number_of_files as f64,
-            total_files: None,
-            suceeded_files: None,
-            failed_files: None,
-        }).unwrap();
+            Err(e) => EachFile { path: file_path, error: Some(e), scheme, ..Default::default() },
+        };
+        reporter.file_finished(file, target.size);
     }
-    
-    if !cancel_flag.load(Ordering::SeqCst) {
-        app.emit("PROGRESS_EVENT", ProgressPayload { 
-            status: "DONE".to_string(),
-            current_file_path: "Done".to_string(), 
-            number_of_files,
-            current_file_number: number_of_files,
-            total_progress: 1.0,
-            total_files: Some(total_files),
-            suceeded_files: Some(suceeded_files),
-            failed_files: Some(failed_files)
-        }).unwrap();
+
+    if cancel_flag.load(Ordering::SeqCst) {
+        let not_started = targets[reporter.files_started()..].iter()
+            .map(|t| t.path.to_string_lossy().to_string())
+            .collect();
+        reporter.cancel(not_started, false);
+    } else {
+        reporter.finish();
     }
+    Ok(())
+}
 
+/******************* 보안 삭제 전 대상 경로의 저장 장치 확인 ******************/
+// 삭제를 시작하기 전에 Copy-on-Write 파일 시스템이나 SSD 처럼 덮어쓰기를 신뢰하기 어려운 경우를 미리 알려주기 위함
+#[derive(serde::Serialize)]
+struct WipeTargetStorage {
+    path: String,
+    storage: StorageInfo,
+}
+
+#[tauri::command]
+fn check_wipe_storage(paths: Vec<String>) -> Vec<WipeTargetStorage> {
+    paths.into_iter()
+        .map(|path| {
+            let storage = storage::detect(Path::new(&path));
+            WipeTargetStorage { path, storage }
+        })
+        .collect()
+}
+
+/******************* 빈 공간 보안 삭제 함수 ******************/
+// 선택한 폴더가 있는 볼륨의 빈 공간을 무작위 데이터로 채웠다가 삭제하여, 이전에 삭제된 파일의 흔적을 덮어씀
+#[tauri::command]
+async fn wipe_free_space(
+    dir_path: String,
+    reserve_bytes: Option<u64>,
+    on_event: Channel<JobEvent>,
+    op_state: State<'_, OperationState>,
+    jobs: State<'_, JobRegistry>,
+    audit: State<'_, AuditLog>,
+) -> Result<(), String> {
+    op_state.is_cancelled.store(false, Ordering::SeqCst);
+    let cancel_flag = op_state.is_cancelled.clone();
+    let reserve_bytes = reserve_bytes.unwrap_or(free_space::DEFAULT_RESERVE_BYTES);
+
+    // 전체 크기는 남은 공간에 따라 작업 중에 바뀜
+    let mut reporter = JobReporter::start(on_event, &jobs, &audit, "FREE_SPACE_WIPE", 1, 0);
+    reporter.file_started(&dir_path, 0);
+    let mut written_bytes = 0u64;
+    let result = free_space::wipe_free_space(Path::new(&dir_path), reserve_bytes, &cancel_flag, &mut |written, total| {
+        written_bytes = written;
+        reporter.set_total(total);
+        reporter.file_progress(written);
+    });
+
+    let file = match result {
+        Ok(written) => EachFile {
+            path: dir_path,
+            note: Some(format!("Overwrote {} MB of free space", written / (1024 * 1024))),
+            ..Default::default()
+        },
+        Err(e) => EachFile { path: dir_path, error: Some(e), ..Default::default() },
+    };
+    reporter.set_total(written_bytes);
+    reporter.file_finished(file, written_bytes);
+    // 임시 파일은 wipe_free_space 안에서 항상 정리되므로 남는 출력 파일이 없음
+    if cancel_flag.load(Ordering::SeqCst) {
+        reporter.cancel(Vec::new(), false);
+    } else {
+        reporter.finish();
+    }
     Ok(())
 }
 
 /******************* 비밀번호 변경 함수 ******************/
 #[tauri::command]
-fn change_password(app: tauri::AppHandle, old_password: String, new_password: String) -> Result<(), String> {
+fn change_password(
+    app: tauri::AppHandle,
+    old_password: String,
+    new_password: String,
+    vault: State<Vault>,
+    audit: State<AuditLog>,
+) -> Result<(), String> {
+    // 잠금 해제된 상태에서만 바꿀 수 있음
+    let unlocked_key = (*vault.key.lock().unwrap()).ok_or("Vault is locked")?;
     let vault_path = get_vault_path(&app)?;
     let vault_data = fs::read(&vault_path).map_err(|e| e.to_string())?;
 
     // 1. 기존 비밀번호로 마스터 키 복호화 시도
-    let vault_key = {
-        if vault_data.len() < 28 { return Err("Invalid vault file".into()); }
-        let salt_bytes = &vault_data[0..16];
-        let nonce_bytes = &vault_data[16..28];
-        let encrypted_vault_key = &vault_data[28..];
-        let salt = SaltString::encode_b64(salt_bytes).map_err(|e| e.to_string())?;
-        let nonce = Nonce::from_slice(nonce_bytes);
-
-        let argon2 = Argon2::new(
-            argon2::Algorithm::Argon2id,
-            Version::V0x13,
-            Params::new(15000, 2, 1, None).unwrap()
-        );
-        let kek_hash = argon2.hash_password(old_password.as_bytes(), &salt).map_err(|e| e.to_string())?;
-        let binding = kek_hash.hash.unwrap();
-        let kek = Key::<Aes256Gcm>::from_slice(&binding.as_bytes()[..32]);
-
-        let cipher = Aes256Gcm::new(kek);
-        cipher.decrypt(nonce, encrypted_vault_key)
-            .map_err(|_| "Password change failed. Old password is incorrect.".to_string())?
-    };
+    let (vault_key, _) = unwrap_vault_key_throttled(&app, &vault_path, &vault_data, &old_password, "Password change failed. Old password is incorrect.")?;
+    if vault_key.as_slice() != unlocked_key.as_slice() {
+        return Err("The vault file does not match the unlocked vault".into());
+    }
 
     // 2. 새로운 비밀번호로 마스터 키 재암호화
-    let mut new_salt_bytes = [0u8; 16];
-    rand::rng().fill_bytes(&mut new_salt_bytes);
-    let new_salt = SaltString::encode_b64(&new_salt_bytes).map_err(|e| e.to_string())?;
-    
-    let argon2 = Argon2::new(
-        argon2::Algorithm::Argon2id,
-        Version::V0x13,
-        Params::new(15000, 2, 1, None).unwrap()
-    );
-    let new_kek_hash = argon2.hash_password(new_password.as_bytes(), &new_salt).map_err(|e| e.to_string())?;
-    let binding = new_kek_hash.hash.unwrap();    
-    let new_kek = Key::<Aes256Gcm>::from_slice(&binding.as_bytes()[..32]);
-
-    let cipher = Aes256Gcm::new(new_kek);
-    let mut new_nonce_bytes = [0u8; 12];
-    rand::rng().fill_bytes(&mut new_nonce_bytes);
-    let new_nonce = Nonce::from_slice(&new_nonce_bytes);
-    let new_encrypted_vault_key = cipher.encrypt(new_nonce, &*vault_key).map_err(|e| e.to_string())?;
-
-    // 3. 새로운 [솔트] + [논스] + [암호화된 마스터 키] 형태로 파일 덮어쓰기
-    let mut final_data = Vec::new();
-    final_data.extend_from_slice(&new_salt_bytes);
-    final_data.extend_from_slice(&new_nonce_bytes);
-    final_data.extend_from_slice(&new_encrypted_vault_key);
-
-    fs::write(vault_path, final_data).map_err(|e| e.to_string())?;
+    let final_data = vault_file::wrap_vault_key(&vault_key, &new_password)?;
+
+    // 3. 이전 비밀번호로 암호화된 백업(<이름>.bak.N)이 남아 있으면 유출된 비밀번호로 마스터 키를 꺼낼 수 있으므로
+    //    백업을 모두 덮어쓴 뒤 삭제하고, 새 내용으로 교체한 뒤 새 비밀번호로 된 백업을 하나 남김
+    //    (교체는 임시 파일을 거치므로 중간에 실패해도 기존 파일은 그대로)
+    let never_cancel = AtomicBool::new(false);
+    vault_file::write_replacing_backups(&vault_path, &final_data, &mut |backup| {
+        wipe::wipe_file(backup, &WipeOptions::default(), &never_cancel, &mut |_| {})
+    })?;
+    // 실패 기록의 키가 vault 파일 내용에서 유도되므로 새 내용으로 다시 저장
+    Throttle::new(&vault_path, &final_data).reset()?;
+    audit.record(AuditEvent::PasswordChanged)?;
+    Ok(())
+}
+
+/******************* 마스터 키 교체 ******************/
+// 마스터 키가 유출되었을 수 있을 때(잠금 해제된 상태의 메모리 덤프 등) 사용. 비밀번호 변경은 같은 키를 다시 암호화할 뿐이므로 도움이 되지 않음
+// 새 마스터 키를 만들고, 이전 키는 선택한 폴더의 파일을 모두 옮길 때까지 복호화에만 사용 (옮기는 작업은 rewrap_files)
+#[tauri::command]
+fn rotate_master_key(
+    app: tauri::AppHandle,
+    password: String,
+    folders: Vec<String>,
+    vault: State<Vault>,
+    audit: State<AuditLog>,
+) -> Result<(), String> {
+    let old_key = (*vault.key.lock().unwrap()).ok_or("Vault is locked")?;
+    if !vault.previous_keys.lock().unwrap().is_empty() {
+        return Err("A key rotation is already in progress. Finish moving files to the current key first.".into());
+    }
+    let vault_path = get_vault_path(&app)?;
+    let vault_data = fs::read(&vault_path).map_err(|e| e.to_string())?;
+    let (stored_key, _) = unwrap_vault_key_throttled(&app, &vault_path, &vault_data, &password, "Incorrect password")?;
+    if stored_key.as_slice() != old_key.as_slice() {
+        return Err("The vault file does not match the unlocked vault".into());
+    }
+
+    // 1. 새 마스터 키를 무작위로 생성
+    let mut new_key_bytes = [0u8; 32];
+    rand::rng().fill_bytes(&mut new_key_bytes);
+    let new_key = Key::<Aes256Gcm>::from(new_key_bytes);
+
+    // 2. 이전 키와 옮길 폴더를 새 키로 암호화해 보관 (vault 파일을 바꾸기 전에 써 두어야 바꾼 직후부터 이전 키를 쓸 수 있음)
+    //    여기서 중단되면 vault 파일은 그대로이고, 새 키로 암호화된 이 목록은 읽을 수 없으므로 무시됨
+    KeyRing::new(&old_key, folders).save(&vault_path, &new_key)?;
+
+    // 3. 새 마스터 키를 비밀번호로 암호화해 vault 파일 교체 (이 시점부터 새 키가 사용됨)
+    let final_data = vault_file::wrap_vault_key(new_key.as_slice(), &password)?;
+    vault_file::write_with_backup(&vault_path, &final_data)?;
+    Throttle::new(&vault_path, &final_data).reset()?;
+    *vault.key.lock().unwrap() = Some(new_key);
+    *vault.previous_keys.lock().unwrap() = vec![old_key];
+
+    // 4. 키 목록과 감사 로그를 새 키로 옮김 (여기서 중단되면 다음 잠금 해제 때 이어서 함)
+    let log_problem = rekey_side_files(&vault_path, &new_key, &[old_key], &audit)?;
+    audit.open(&vault_path, &new_key)?;
+    audit.record(AuditEvent::KeyRotated {
+        key_id: data_key::key_id(&new_key),
+        previous_key_id: data_key::key_id(&old_key),
+        log_problem,
+    })?;
+    Ok(())
+}
+
+/******************* 키 목록과 감사 로그를 현재 마스터 키로 옮김 ******************/
+// 이미 현재 키로 되어 있으면 아무것도 하지 않음
+// 감사 로그를 옮기면서 검증에 실패해 버린 부분이 있었다면 그 이유를 반환
+fn rekey_side_files(
+    vault_path: &Path,
+    vault_key: &Key<Aes256Gcm>,
+    previous_keys: &[Key<Aes256Gcm>],
+    audit: &AuditLog,
+) -> Result<Option<String>, String> {
+    if KeyBook::load(vault_path, vault_key).is_err() {
+        if let Some(keybook) = previous_keys.iter().find_map(|key| KeyBook::load(vault_path, key).ok()) {
+            keybook.save(vault_path, vault_key)?;
+        }
+    }
+    for previous_key in previous_keys {
+        if audit::encrypted_with(vault_path, previous_key) {
+            return audit.rekey(vault_path, previous_key, vault_key);
+        }
+    }
+    Ok(None)
+}
+
+// 키 교체 중이면 이전 마스터 키들 (목록을 읽을 수 없으면 교체 도중 중단된 것일 수 있으므로, 호출한 쪽에서 알리고 이전 키 없이 계속함)
+fn load_previous_keys(vault_path: &Path, vault_key: &Key<Aes256Gcm>) -> Result<Vec<Key<Aes256Gcm>>, String> {
+    KeyRing::load(vault_path, vault_key).map(|keyring| keyring.keys())
+}
+
+/******************* 키 교체 진행 상황 ******************/
+#[tauri::command]
+fn get_rotation_status(app: tauri::AppHandle, vault: State<Vault>) -> Result<RotationStatus, String> {
+    let vault_key = (*vault.key.lock().unwrap()).ok_or("Vault is locked")?;
+    let vault_path = get_vault_path(&app)?;
+    let keyring = KeyRing::load(&vault_path, &vault_key).unwrap_or_default();
+    Ok(keyring.status(&vault_key))
+}
+
+/******************* 선택한 폴더의 .enc 파일을 현재 마스터 키로 옮김 ******************/
+// 키 교체 후 백그라운드로 실행. 이미 옮긴 파일은 건너뛰므로, 중단되었으면 다시 실행해서 이어서 할 수 있음
+// 실패 없이 끝나면 이전 키를 폐기함
+#[tauri::command]
+async fn rewrap_files(
+    app: tauri::AppHandle,
+    on_event: Channel<JobEvent>,
+    vault: State<'_, Vault>,
+    op_state: State<'_, OperationState>,
+    jobs: State<'_, JobRegistry>,
+    audit: State<'_, AuditLog>,
+) -> Result<(), String> {
+    let vault_key = (*vault.key.lock().unwrap()).ok_or("Vault is locked")?;
+    let previous_keys = vault.previous_keys.lock().unwrap().clone();
+    if previous_keys.is_empty() {
+        return Err("No key rotation is in progress".into());
+    }
+    let vault_path = get_vault_path(&app)?;
+    let keyring = KeyRing::load(&vault_path, &vault_key)?;
+    op_state.is_cancelled.store(false, Ordering::SeqCst);
+    let cancel_flag = op_state.is_cancelled.clone();
+
+    let (files, walk_errors) = rotation::collect_files(keyring.folders());
+    let total_size: u64 = files.iter().map(|p| fs::metadata(p).map(|m| m.len()).unwrap_or(0)).sum();
+    let mut reporter = JobReporter::start(on_event, &jobs, &audit, "REWRAP", files.len(), total_size);
+    // 읽지 못한 폴더가 있으면 그 안의 파일이 남아 있을 수 있으므로 이전 키를 폐기하지 않음
+    let mut complete = walk_errors.is_empty();
+    for (path, error) in walk_errors {
+        reporter.file_failed_early(EachFile { path, error: Some(error), ..Default::default() });
+    }
+
+    for path in files.iter() {
+        if cancel_flag.load(Ordering::SeqCst) { break; }
+
+        let file_path = path.to_string_lossy().to_string();
+        let file_size = fs::metadata(path).map(|m| m.len()).unwrap_or(0);
+        reporter.file_started(&file_path, file_size);
+        // 원래 파일을 제자리에서 바꾸므로 output 은 비워 둠 (취소해도 지울 출력 파일이 없음)
+        let file = match rotation::rewrap_file(path, &vault_key, &previous_keys) {
+            Ok(note) => EachFile { path: file_path, note: Some(note), ..Default::default() },
+            Err(e) => {
+                complete = false;
+                EachFile { path: file_path, error: Some(e), ..Default::default() }
+            }
+        };
+        reporter.file_finished(file, file_size);
+    }
+
+    if cancel_flag.load(Ordering::SeqCst) {
+        let not_started = files[reporter.files_started()..].iter().map(|p| p.to_string_lossy().to_string()).collect();
+        reporter.cancel(not_started, false);
+        return Ok(());
+    }
+    reporter.finish();
+    if complete {
+        retire_previous_keys(&vault_path, &keyring.status(&vault_key).previous_key_ids, &vault, &audit)?;
+    }
     Ok(())
 }
 
+/******************* 이전 마스터 키 폐기 ******************/
+// 이전 키 목록과, 이전 키가 들어 있을 수 있는 vault 파일 백업(<이름>.bak.N)을 덮어쓴 뒤 삭제
+fn retire_previous_keys(vault_path: &Path, key_ids: &[String], vault: &Vault, audit: &AuditLog) -> Result<(), String> {
+    let never_cancel = AtomicBool::new(false);
+    let mut secrets = vault_file::backup_paths(vault_path);
+    secrets.push(rotation::keyring_path(vault_path));
+    for path in secrets.iter().filter(|p| p.is_file()) {
+        wipe::wipe_file(path, &WipeOptions::default(), &never_cancel, &mut |_| {})?;
+    }
+    vault.previous_keys.lock().unwrap().clear();
+    audit.record(AuditEvent::KeysRetired { key_ids: key_ids.to_vec() })?;
+    Ok(())
+}
+
+/******************* 폴더를 대상 폴더에 .enc 파일로 미러 ******************/
+// 대상 폴더의 목록(mirror::Manifest)과 비교해서 새 파일과 바뀐 파일만 암호화하고, 원본에서 지워진 파일의 출력은 삭제함
+// 출력 이름은 매번 같으므로 클라우드 동기화 폴더에 두어도 바뀐 파일만 다시 올라감
+#[tauri::command]
+#[allow(clippy::too_many_arguments)] // 상태(State)는 Tauri 가 주입하므로 구조체로 묶을 수 없음
+async fn mirror_folder(
+    app: tauri::AppHandle,
+    vault: State<'_, Vault>,
+    source_dir: String,
+    destination_dir: String,
+    on_event: Channel<JobEvent>,
+    op_state: State<'_, OperationState>,
+    jobs: State<'_, JobRegistry>,
+    audit: State<'_, AuditLog>,
+) -> Result<(), String> {
+    let vault_key = (*vault.key.lock().unwrap()).ok_or("Vault is locked")?;
+    let vault_keys: Vec<Key<Aes256Gcm>> = std::iter::once(vault_key).chain(vault.previous_keys.lock().unwrap().iter().copied()).collect();
+    let cipher = AppSettings::load(&get_settings_path(&app)?).default_cipher;
+    let source = fs::canonicalize(&source_dir).map_err(|e| e.to_string())?;
+    let destination = fs::canonicalize(&destination_dir).map_err(|e| e.to_string())?;
+    // 대상 폴더가 원본 안에 있으면 출력이 다음 미러의 원본이 됨
+    if destination.starts_with(&source) {
+        return Err("The destination folder must be outside the source folder".into());
+    }
+    let mut manifest = Manifest::load(&destination, &source, &vault_keys)?;
+    op_state.is_cancelled.store(false, Ordering::SeqCst);
+    let cancel_flag = op_state.is_cancelled.clone();
+
+    let (sources, walk_errors) = mirror::collect_sources(&source);
+    // 읽지 못한 폴더가 있으면 그 안의 파일이 지워졌는지 알 수 없으므로 출력을 지우지 않음
+    let deleted = if walk_errors.is_empty() { manifest.deleted_sources(&sources) } else { Vec::new() };
+    let total_size: u64 = sources.iter().map(|s| s.size).sum();
+    let mut reporter = JobReporter::start(on_event, &jobs, &audit, "MIRROR", sources.len() + deleted.len(), total_size);
+    for (path, error) in walk_errors {
+        reporter.file_failed_early(EachFile { path, error: Some(error), ..Default::default() });
+    }
+
+    for source_file in sources.iter() {
+        if cancel_flag.load(Ordering::SeqCst) { break; }
+
+        let file_path = source_file.path.to_string_lossy().to_string();
+        reporter.file_started(&file_path, source_file.size);
+        // 기존 출력을 제자리에서 바꾸므로 output 은 비워 둠 (취소해도 미러의 출력 파일을 지우지 않도록)
+        let result = mirror_file(source_file, &destination, &mut manifest, cipher, &vault_key, &mut |bytes| {
+            reporter.file_progress(bytes);
+        });
+        let file = match result {
+            Ok(note) => EachFile { path: file_path, note: Some(note), ..Default::default() },
+            Err(e) => EachFile { path: file_path, error: Some(e), ..Default::default() },
+        };
+        reporter.file_finished(file, source_file.size);
+    }
+
+    // 출력은 암호문이므로 덮어쓰지 않고 그냥 삭제함
+    let cancelled = cancel_flag.load(Ordering::SeqCst);
+    if !cancelled {
+        for relative_path in deleted.iter() {
+            let file_path = source.join(relative_path).to_string_lossy().to_string();
+            reporter.file_started(&file_path, 0);
+            let output = manifest.get(relative_path).map(|entry| entry.output.clone()).unwrap_or_default();
+            let file = match fs::remove_file(destination.join(&output)) {
+                Err(e) if e.kind() != std::io::ErrorKind::NotFound => EachFile { path: file_path, error: Some(e.to_string()), ..Default::default() },
+                _ => {
+                    manifest.remove(relative_path);
+                    EachFile { path: file_path, note: Some(format!("Source deleted: removed {}", output)), ..Default::default() }
+                }
+            };
+            reporter.file_finished(file, 0);
+        }
+    }
+
+    // 취소해도 이미 처리한 파일은 목록에 남겨서 다음 미러 때 다시 암호화하지 않음
+    if let Err(e) = manifest.save(&destination, &vault_key) {
+        let path = mirror::manifest_path(&destination).to_string_lossy().to_string();
+        reporter.file_failed_early(EachFile { path, error: Some(e), ..Default::default() });
+    }
+    if cancelled {
+        let not_started = sources[reporter.files_started()..]
+            .iter()
+            .map(|s| s.path.to_string_lossy().to_string())
+            .chain(deleted.iter().map(|relative_path| source.join(relative_path).to_string_lossy().to_string()))
+            .collect();
+        reporter.cancel(not_started, false);
+    } else {
+        reporter.finish();
+    }
+    Ok(())
+}
+
+// 원본 파일 하나를 미러하고 보고서에 남길 처리 내용을 반환
+// 크기와 수정 시각이 목록과 같으면 읽지 않고 건너뛰고, 다르더라도 내용이 같으면 목록의 수정 시각만 고침
+fn mirror_file(
+    source_file: &mirror::SourceFile,
+    destination: &Path,
+    manifest: &mut Manifest,
+    cipher: Cipher,
+    vault_key: &Key<Aes256Gcm>,
+    on_progress: &mut dyn FnMut(u64),
+) -> Result<String, String> {
+    let previous = manifest.get(&source_file.relative_path).cloned();
+    let output_exists = |entry: &mirror::ManifestEntry| destination.join(&entry.output).is_file();
+    if let Some(entry) = &previous {
+        if entry.size == source_file.size && entry.modified == source_file.modified && output_exists(entry) {
+            return Ok("Unchanged".to_string());
+        }
+    }
+
+    // 읽기와 쓰기에 각각 파일 크기의 절반씩 진행률을 배분
+    let file_size = source_file.size;
+    let half_size = file_size / 2;
+    let original_data = progress::read_with_progress(&source_file.path, &mut |read_bytes| {
+        on_progress(progress::scale(read_bytes, file_size, half_size));
+    })?;
+    let hash = mirror::content_hash(&original_data);
+    let output = previous.as_ref().map_or_else(|| mirror::output_name(vault_key, &source_file.relative_path), |entry| entry.output.clone());
+    let entry = mirror::ManifestEntry { size: original_data.len() as u64, modified: source_file.modified, hash, output };
+    if previous.as_ref().is_some_and(|previous| previous.hash == entry.hash && output_exists(previous)) {
+        manifest.insert(&source_file.relative_path, entry);
+        return Ok("Unchanged".to_string());
+    }
+
+    // 복호화할 때 폴더 구조를 되살릴 수 있도록 파일 이름 대신 상대 경로를 저장 (복호화할 때 거부될 경로면 미리 실패)
+    output_relative_path(&source_file.relative_path)?;
+    let metadata = FileMetadata { original_filename: source_file.relative_path.clone() };
+    let metadata_bytes = serde_json::to_vec(&metadata).map_err(|e| e.to_string())?;
+    let file_key = data_key::generate();
+    let header = FileHeader::new(cipher, vec![data_key::wrap(&file_key, vault_key)?]);
+    let mut final_data = envelope::write_header(&header)?;
+    final_data.extend_from_slice(&envelope::seal_body(&header, &file_key, &metadata_bytes, &original_data)?);
+
+    let output_size = final_data.len() as u64;
+    progress::write_with_progress(&destination.join(&entry.output), &final_data, &mut |written_bytes| {
+        on_progress(half_size + progress::scale(written_bytes, output_size, file_size - half_size));
+    })?;
+    let note = match previous {
+        Some(_) => format!("Changed: encrypted to {}", entry.output),
+        None => format!("New: encrypted to {}", entry.output),
+    };
+    manifest.insert(&source_file.relative_path, entry);
+    Ok(note)
+}
+
+/******************* 내 X25519 키 쌍과 동료 공개키 주소록 ******************/
+// 잠금 해제된 vault 의 키 목록과, 저장할 때 필요한 vault 경로/마스터 키
+fn open_keybook(app: &tauri::AppHandle, vault: &Vault) -> Result<(KeyBook, PathBuf, Key<Aes256Gcm>), String> {
+    let vault_key = (*vault.key.lock().unwrap()).ok_or("Vault is locked")?;
+    let vault_path = get_vault_path(app)?;
+    let keybook = KeyBook::load(&vault_path, &vault_key)?;
+    Ok((keybook, vault_path, vault_key))
+}
+
+#[tauri::command]
+fn list_identities(app: tauri::AppHandle, vault: State<Vault>) -> Result<Vec<IdentityInfo>, String> {
+    Ok(open_keybook(&app, &vault)?.0.identities())
+}
+
+#[tauri::command]
+fn create_identity(app: tauri::AppHandle, name: String, vault: State<Vault>, audit: State<AuditLog>) -> Result<IdentityInfo, String> {
+    let (mut keybook, vault_path, vault_key) = open_keybook(&app, &vault)?;
+    let created = keybook.create_identity(&name);
+    keybook.save(&vault_path, &vault_key)?;
+    audit.record(AuditEvent::IdentityCreated { public_key: created.public_key.clone() })?;
+    Ok(created)
+}
+
+// 이 키 쌍으로만 열 수 있던 파일은 더 이상 복호화할 수 없음
+#[tauri::command]
+fn delete_identity(app: tauri::AppHandle, id: String, vault: State<Vault>, audit: State<AuditLog>) -> Result<(), String> {
+    let (mut keybook, vault_path, vault_key) = open_keybook(&app, &vault)?;
+    let removed = keybook.remove_identity(&id)?;
+    keybook.save(&vault_path, &vault_key)?;
+    audit.record(AuditEvent::IdentityDeleted { public_key: removed.public_key })?;
+    Ok(())
+}
+
+#[tauri::command]
+fn list_contacts(app: tauri::AppHandle, vault: State<Vault>) -> Result<Vec<Contact>, String> {
+    Ok(open_keybook(&app, &vault)?.0.contacts().to_vec())
+}
+
+#[tauri::command]
+fn add_contact(app: tauri::AppHandle, name: String, public_key: String, vault: State<Vault>, audit: State<AuditLog>) -> Result<Contact, String> {
+    let (mut keybook, vault_path, vault_key) = open_keybook(&app, &vault)?;
+    let contact = keybook.add_contact(&name, &public_key)?;
+    keybook.save(&vault_path, &vault_key)?;
+    audit.record(AuditEvent::ContactAdded { name: contact.name.clone(), public_key: contact.public_key.clone() })?;
+    Ok(contact)
+}
+
+#[tauri::command]
+fn remove_contact(app: tauri::AppHandle, id: String, vault: State<Vault>, audit: State<AuditLog>) -> Result<(), String> {
+    let (mut keybook, vault_path, vault_key) = open_keybook(&app, &vault)?;
+    let removed = keybook.remove_contact(&id)?;
+    keybook.save(&vault_path, &vault_key)?;
+    audit.record(AuditEvent::ContactRemoved { name: removed.name, public_key: removed.public_key })?;
+    Ok(())
+}
+
+/******************* vault 헤더(암호화된 마스터 키)를 다른 위치로 내보내기 ******************/
+// vault 파일이 손상되거나 삭제되면 모든 .enc 파일을 복구할 수 없으므로 따로 보관할 수 있게 함
+// export_password 를 주면 그 비밀번호로 다시 암호화하고, 없으면 현재 비밀번호로 암호화된 파일을 그대로 복사
+// 어느 쪽이든 결과는 vault 파일과 같은 형식이라 import_vault_header 나 open_vault 로 바로 쓸 수 있음
+#[tauri::command]
+fn export_vault_header(
+    app: tauri::AppHandle,
+    path: String,
+    export_password: Option<String>,
+    vault: State<Vault>,
+    audit: State<AuditLog>,
+) -> Result<(), String> {
+    let vault_key = (*vault.key.lock().unwrap()).ok_or("Vault is locked")?;
+    let vault_path = get_vault_path(&app)?;
+    let export_path = PathBuf::from(&path);
+    if export_path == vault_path {
+        return Err("Choose a location other than the vault file itself".into());
+    }
+
+    let data = match &export_password {
+        Some(export_password) => vault_file::wrap_vault_key(vault_key.as_slice(), export_password)?,
+        None => fs::read(&vault_path).map_err(|e| e.to_string())?,
+    };
+    progress::write_with_progress(&export_path, &data, &mut |_| {})?;
+    audit.record(AuditEvent::HeaderExported { rewrapped: export_password.is_some() })?;
+    Ok(())
+}
+
+/******************* 내보낸 헤더나 자동 백업(<이름>.bak.N)으로 vault 복원 ******************/
+// password 는 헤더를 암호화한 비밀번호(내보낼 때의 비밀번호), new_password 를 주면 그 비밀번호로 다시 암호화
+// path 가 없으면 선택된 vault 위치에 복원하고, 그 위치에 같은 마스터 키의 vault 가 있으면 백업으로 옮긴 뒤 교체
+// 복원에 성공하면 바로 잠금 해제된 상태가 됨
+#[tauri::command]
+#[allow(clippy::too_many_arguments)] // 상태(State)는 Tauri 가 주입하므로 구조체로 묶을 수 없음
+fn import_vault_header(
+    app: tauri::AppHandle,
+    source: String,
+    password: String,
+    new_password: Option<String>,
+    path: Option<String>,
+    name: Option<String>,
+    vault_state: State<Vault>,
+    audit: State<AuditLog>,
+) -> Result<(), String> {
+    let source_data = fs::read(&source).map_err(|e| e.to_string())?;
+    // 가져올 헤더는 사용자가 고른 백업 파일이므로 잠금 해제 정책(실패 기록, 삭제)을 적용하지 않고,
+    // 그 파일 옆에 실패 기록이나 감사 로그 파일도 만들지 않음
+    let vault_key_bytes = vault_file::unwrap_vault_key(&source_data, &password)?
+        .ok_or("Incorrect password for this vault header")?;
+    let vault_key = *Key::<Aes256Gcm>::from_slice(&vault_key_bytes);
+    let data = match &new_password {
+        Some(new_password) => vault_file::wrap_vault_key(&vault_key_bytes, new_password)?,
+        None => source_data,
+    };
+
+    let config_dir = get_config_dir(&app)?;
+    let mut registry = VaultRegistry::load(&config_dir)?;
+    let explicit_path = path.is_some();
+    let vault_path = match (path, registry.selected()) {
+        (Some(path), _) => PathBuf::from(path),
+        (None, Some(selected)) => PathBuf::from(&selected.path),
+        (None, None) => config_dir.join("vault.key"),
+    };
+    if Path::new(&source) == vault_path {
+        return Err("The header is already at this location".into());
+    }
+    // 위치를 정하지 않았는데 그 자리에 vault 가 있으면, 같은 마스터 키의 vault 일 때만 교체함
+    // (잠금 해제된 키나 가져올 헤더의 비밀번호로 연 키와 비교). 다른 vault 를 덮어쓰려면 위치를 직접 골라야 함
+    if !explicit_path && vault_path.exists() {
+        let unlocked = *vault_state.key.lock().unwrap();
+        let same_vault = unlocked == Some(vault_key)
+            || fs::read(&vault_path)
+                .ok()
+                .and_then(|existing| vault_file::unwrap_vault_key(&existing, &password).ok().flatten())
+                .is_some_and(|existing_key| existing_key == vault_key_bytes);
+        if !same_vault {
+            return Err("A different vault already exists at this location. Choose a new location to restore the header.".into());
+        }
+    }
+    // 헤더나 로그가 이미 있던 위치면 기존 로그를 이어 쓰고(마지막 기록 확인), 아니면 새 로그를 시작함
+    let existing = vault_path.exists() || audit::side_paths(&vault_path).0.exists();
+    vault_file::write_with_backup(&vault_path, &data)?;
+    Throttle::new(&vault_path, &data).reset()?;
+    registry.add(name, &vault_path);
+    registry.save()?;
+
+    *vault_state.key.lock().unwrap() = Some(vault_key);
+    // 가져온 헤더 옆에 읽을 수 없는 이전 키 목록이 있어도 복원은 계속함 (잠금 해제할 때 다시 알려줌)
+    *vault_state.previous_keys.lock().unwrap() = load_previous_keys(&vault_path, &vault_key).unwrap_or_default();
+    if existing {
+        audit.open(&vault_path, &vault_key)?;
+    } else {
+        audit.create(&vault_path, &vault_key)?;
+    }
+    audit.record(AuditEvent::HeaderImported)?;
+    Ok(())
+}
+
+/******************* 앱 설정 읽기/저장 ******************/
+#[tauri::command]
+fn get_settings(app: tauri::AppHandle) -> Result<AppSettings, String> {
+    Ok(AppSettings::load(&get_settings_path(&app)?))
+}
+
+// 잠금 해제 정책을 바꿀 수 있으므로 잠금 해제된 상태에서만 저장
+#[tauri::command]
+fn save_settings(app: tauri::AppHandle, settings: AppSettings, vault: State<Vault>, audit: State<AuditLog>) -> Result<(), String> {
+    if vault.key.lock().unwrap().is_none() {
+        return Err("Vault is locked".into());
+    }
+    settings.unlock_policy.validate()?;
+    settings.save(&get_settings_path(&app)?)?;
+    audit.record(AuditEvent::SettingsChanged)?;
+    Ok(())
+}
+
+/******************* 감사 로그를 읽고 변경 여부를 검증 (잠금 해제 후에만 가능) ******************/
+#[tauri::command]
+fn read_audit_log(audit: State<AuditLog>) -> Result<audit::AuditLogView, String> {
+    audit.read()
+}
+
 fn main() {
     tauri::Builder::default()
         .plugin(tauri_plugin_dialog::init())
-        .manage(Vault { key: Default::default() })
+        .manage(Vault { key: Default::default(), previous_keys: Default::default() })
         .manage(OperationState { is_cancelled: Arc::new(AtomicBool::new(false)) })
+        .manage(JobRegistry::default())
+        .manage(AuditLog::default())
         .invoke_handler(tauri::generate_handler![
             vault_exists,
+            list_vaults,
+            open_vault,
+            switch_vault,
+            forget_vault,
+            lock_vault,
             create_vault,
             unlock_vault,
+            get_unlock_status,
             get_files_in_dir_recursive,
+            scan_folder,
             encrypt_files, 
             decrypt_files, 
+            files_need_password,
+            rotate_master_key,
+            get_rotation_status,
+            rewrap_files,
+            mirror_folder,
             secure_delete_files,
+            check_wipe_storage,
+            wipe_free_space,
             cancel_operation,
-            change_password,        
+            remove_job_outputs,
+            keep_job_outputs,
+            export_job_report,
+            change_password,
+            export_vault_header,
+            import_vault_header,
+            read_audit_log,
+            list_identities,
+            create_identity,
+            delete_identity,
+            list_contacts,
+            add_contact,
+            remove_contact,
+            get_settings,
+            save_settings,
         ])
         .run(tauri::generate_context!())
         .expect("error while running tauri application");
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn output_file_name_rejects_paths() {
+        assert_eq!(output_file_name("report.pdf"), Ok("report.pdf"));
+        assert_eq!(output_file_name(".bashrc"), Ok(".bashrc"));
+        for name in ["", ".", "..", "../../.bashrc", "/etc/passwd", "a/b.txt", "..\\evil.txt", "C:\\evil.txt"] {
+            assert!(output_file_name(name).is_err(), "{:?} should be rejected", name);
+        }
+    }
+
+    // 파일마다 데이터 키를 만들기 전의 형식: 헤더 없이 vault 키와 AES-256-GCM 으로 AAD 없이 암호화
+    fn baseline_file(vault_key: &Key<Aes256Gcm>, metadata: &[u8], content: &[u8]) -> Vec<u8> {
+        use aes_gcm::aead::{Aead, AeadCore, KeyInit, OsRng};
+        let cipher = Aes256Gcm::new(vault_key);
+        let metadata_nonce = Aes256Gcm::generate_nonce(&mut OsRng);
+        let encrypted_metadata = cipher.encrypt(&metadata_nonce, metadata).unwrap();
+        let content_nonce = Aes256Gcm::generate_nonce(&mut OsRng);
+        let encrypted_content = cipher.encrypt(&content_nonce, content).unwrap();
+        let mut data = metadata_nonce.to_vec();
+        data.extend_from_slice(&(encrypted_metadata.len() as u16).to_be_bytes());
+        data.extend_from_slice(&encrypted_metadata);
+        data.extend_from_slice(&content_nonce);
+        data.extend_from_slice(&encrypted_content);
+        data
+    }
+
+    #[test]
+    fn headerless_file_opens_with_master_keys() {
+        let vault_key = Key::<Aes256Gcm>::from([3u8; 32]);
+        let previous_key = Key::<Aes256Gcm>::from([4u8; 32]);
+        let data = baseline_file(&previous_key, b"metadata", b"content");
+        assert!(envelope::read_header(&data).is_none());
+        let opened = open_with_master_keys(&[vault_key, previous_key], &data).unwrap();
+        assert_eq!(opened, (b"metadata".to_vec(), b"content".to_vec()));
+        assert!(open_with_master_keys(&[vault_key], &data).is_err());
+    }
+
+    #[test]
+    fn header_without_recipients_is_rejected() {
+        let vault_key = Key::<Aes256Gcm>::from([3u8; 32]);
+        let header = FileHeader { cipher: Cipher::XChaCha20Poly1305, ..FileHeader::default() };
+        let mut data = envelope::write_header(&header).unwrap();
+        data.extend_from_slice(&envelope::seal_body(&header, &vault_key, b"metadata", b"content").unwrap());
+
+        let (header, _) = envelope::read_header(&data).unwrap();
+        assert!(header.validate().unwrap_err().starts_with("Invalid file"));
+    }
+
+    #[test]
+    fn output_relative_path_keeps_subfolders_inside_destination() {
+        assert_eq!(output_relative_path("report.pdf"), Ok(PathBuf::from("report.pdf")));
+        assert_eq!(output_relative_path("docs/2024/report.pdf"), Ok(Path::new("docs").join("2024").join("report.pdf")));
+        for name in ["", "/", "docs/", "/etc/passwd", "docs//report.pdf", "docs/../../.bashrc", "./report.pdf", "docs\\..\\evil.txt"] {
+            assert!(output_relative_path(name).is_err(), "{:?} should be rejected", name);
+        }
+    }
+}
@@ -0,0 +1,185 @@
+use aes_gcm::aead::{Aead, AeadCore, OsRng, Payload};
+use aes_gcm::{Aes256Gcm, Key, KeyInit, Nonce};
+use hmac::{Hmac, Mac};
+use serde::{Deserialize, Serialize};
+use sha2::{Digest, Sha256};
+use std::collections::{BTreeMap, HashSet};
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::UNIX_EPOCH;
+use walkdir::WalkDir;
+
+use crate::progress;
+use crate::subkey::{self, Purpose};
+
+// 미러 대상 폴더에 두는 목록 파일 이름
+const MANIFEST_NAME: &str = ".securevault-mirror";
+const MANIFEST_AAD: &[u8] = b"securevault mirror manifest";
+const NONCE_LEN: usize = 12;
+// 출력 이름에 쓰는 HMAC 앞부분 길이 (바이트)
+const OUTPUT_NAME_LEN: usize = 16;
+
+// 원본 파일 하나의 마지막 미러 상태
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ManifestEntry {
+    pub size: u64,
+    pub modified: u64, // 수정 시각 (UNIX 시각, 나노초)
+    pub hash: String,  // 원본 내용의 SHA-256 (hex)
+    pub output: String, // 대상 폴더 안의 .enc 파일 이름
+}
+
+/******************* 폴더 미러 목록 (대상 폴더의 .securevault-mirror) ******************/
+// 원본 폴더 기준 상대 경로마다 크기, 수정 시각, 해시, 출력 이름을 기록해서 바뀐 파일만 다시 암호화함
+// 경로와 해시가 드러나지 않도록 마스터 키에서 유도한 목록 키로 암호화해서 저장함
+// [논스(12)] + [암호화된 JSON]
+#[derive(Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Manifest {
+    source: String, // 이 대상 폴더에 미러하는 원본 폴더 (다른 폴더를 같은 곳에 미러해서 출력을 지우는 일을 막음)
+    entries: BTreeMap<String, ManifestEntry>,
+}
+
+impl Manifest {
+    // 파일이 없으면 처음 미러하는 것. 마스터 키를 교체하는 중이면 이전 키로 저장된 목록도 열 수 있도록 키 목록을 받음
+    pub fn load(destination: &Path, source: &Path, vault_keys: &[Key<Aes256Gcm>]) -> Result<Self, String> {
+        let path = manifest_path(destination);
+        if !path.exists() {
+            return Ok(Manifest { source: source.to_string_lossy().to_string(), entries: BTreeMap::new() });
+        }
+        let data = fs::read(&path).map_err(|e| e.to_string())?;
+        if data.len() < NONCE_LEN { return Err("Mirror list is damaged".into()); }
+        let json = decrypt(&data, vault_keys).ok_or("Decryption failed: the mirror list in this folder belongs to a different vault")?;
+        let manifest: Manifest = serde_json::from_slice(&json).map_err(|e| format!("Mirror list is damaged: {}", e))?;
+        if Path::new(&manifest.source) != source {
+            return Err(format!("This folder already mirrors another folder: {}", manifest.source));
+        }
+        Ok(manifest)
+    }
+
+    // 항상 현재 마스터 키로 저장
+    pub fn save(&self, destination: &Path, vault_key: &Key<Aes256Gcm>) -> Result<(), String> {
+        let json = serde_json::to_vec(self).map_err(|e| e.to_string())?;
+        let cipher = Aes256Gcm::new(&subkey::derive(vault_key, Purpose::Catalog));
+        let nonce = Aes256Gcm::generate_nonce(&mut OsRng);
+        let encrypted = cipher.encrypt(&nonce, Payload { msg: &json, aad: MANIFEST_AAD }).map_err(|e| e.to_string())?;
+        let mut data = nonce.to_vec();
+        data.extend_from_slice(&encrypted);
+        progress::write_with_progress(&manifest_path(destination), &data, &mut |_| {})
+    }
+
+    pub fn get(&self, relative_path: &str) -> Option<&ManifestEntry> {
+        self.entries.get(relative_path)
+    }
+
+    pub fn insert(&mut self, relative_path: &str, entry: ManifestEntry) {
+        self.entries.insert(relative_path.to_string(), entry);
+    }
+
+    pub fn remove(&mut self, relative_path: &str) -> Option<ManifestEntry> {
+        self.entries.remove(relative_path)
+    }
+
+    // 원본 폴더에 더 이상 없는 파일의 상대 경로
+    pub fn deleted_sources(&self, sources: &[SourceFile]) -> Vec<String> {
+        let present: HashSet<&str> = sources.iter().map(|s| s.relative_path.as_str()).collect();
+        self.entries.keys().filter(|path| !present.contains(path.as_str())).cloned().collect()
+    }
+}
+
+pub fn manifest_path(destination: &Path) -> PathBuf {
+    destination.join(MANIFEST_NAME)
+}
+
+pub fn is_manifest(path: &Path) -> bool {
+    path.file_name().is_some_and(|name| name == MANIFEST_NAME)
+}
+
+// 마스터 키를 교체한 뒤 이전 키로 저장된 목록을 현재 키로 다시 저장 (rotation::rewrap_file 에서 사용)
+// 이전 키를 폐기한 뒤에도 미러를 이어서 할 수 있도록 함. 결과로 보고서에 남길 처리 내용을 반환
+pub fn rekey_manifest(path: &Path, vault_key: &Key<Aes256Gcm>, previous_keys: &[Key<Aes256Gcm>]) -> Result<String, String> {
+    let data = fs::read(path).map_err(|e| e.to_string())?;
+    if data.len() < NONCE_LEN { return Err("Mirror list is damaged".into()); }
+    if decrypt(&data, std::slice::from_ref(vault_key)).is_some() {
+        return Ok("Already uses the current key".to_string());
+    }
+    let Some(json) = decrypt(&data, previous_keys) else {
+        return Ok("Skipped: not encrypted with this vault's keys".to_string());
+    };
+    let manifest: Manifest = serde_json::from_slice(&json).map_err(|e| format!("Mirror list is damaged: {}", e))?;
+    let destination = path.parent().ok_or("Invalid mirror list path")?;
+    manifest.save(destination, vault_key)?;
+    Ok("Moved to the current key".to_string())
+}
+
+// 키 중 하나로 열리면 JSON
+fn decrypt(data: &[u8], vault_keys: &[Key<Aes256Gcm>]) -> Option<Vec<u8>> {
+    vault_keys.iter().find_map(|key| {
+        let cipher = Aes256Gcm::new(&subkey::derive(key, Purpose::Catalog));
+        let payload = Payload { msg: &data[NONCE_LEN..], aad: MANIFEST_AAD };
+        cipher.decrypt(Nonce::from_slice(&data[..NONCE_LEN]), payload).ok()
+    })
+}
+
+// 원본 폴더 안의 파일 하나
+pub struct SourceFile {
+    pub path: PathBuf,
+    pub relative_path: String, // 구분자는 OS 와 상관없이 '/'
+    pub size: u64,
+    pub modified: u64,
+}
+
+/******************* 원본 폴더의 파일 목록 ******************/
+// 링크는 따라가지 않고 일반 파일만 모음. 읽을 수 없는 항목은 (경로, 이유) 로 따로 반환
+pub fn collect_sources(source: &Path) -> (Vec<SourceFile>, Vec<(String, String)>) {
+    let mut files = Vec::new();
+    let mut errors = Vec::new();
+    for entry in WalkDir::new(source).follow_root_links(false).sort_by_file_name() {
+        let entry = match entry {
+            Ok(entry) => entry,
+            Err(e) => {
+                let path = e.path().map(|p| p.to_string_lossy().to_string()).unwrap_or_else(|| source.to_string_lossy().to_string());
+                errors.push((path, e.to_string()));
+                continue;
+            }
+        };
+        if !entry.file_type().is_file() {
+            continue;
+        }
+        let metadata = match entry.metadata() {
+            Ok(metadata) => metadata,
+            Err(e) => {
+                errors.push((entry.path().to_string_lossy().to_string(), e.to_string()));
+                continue;
+            }
+        };
+        let Ok(relative) = entry.path().strip_prefix(source) else { continue };
+        let relative_path = relative.components().map(|c| c.as_os_str().to_string_lossy()).collect::<Vec<_>>().join("/");
+        files.push(SourceFile {
+            path: entry.path().to_path_buf(),
+            relative_path,
+            size: metadata.len(),
+            modified: metadata
+                .modified()
+                .ok()
+                .and_then(|time| time.duration_since(UNIX_EPOCH).ok())
+                .map_or(0, |d| d.as_nanos() as u64),
+        });
+    }
+    (files, errors)
+}
+
+/******************* 출력 파일 이름 ******************/
+// 상대 경로의 HMAC 이라 같은 파일은 매번 같은 이름으로 저장되어 동기화 프로그램이 바뀐 파일만 올림
+// 이름만으로는 원래 경로를 알 수 없음. 한 번 정한 이름은 목록에 기록되므로 마스터 키를 바꿔도 유지됨
+pub fn output_name(vault_key: &Key<Aes256Gcm>, relative_path: &str) -> String {
+    let mut mac = <Hmac<Sha256> as Mac>::new_from_slice(&subkey::derive(vault_key, Purpose::Filename)).expect("HMAC accepts any key length");
+    mac.update(relative_path.as_bytes());
+    let digest = mac.finalize().into_bytes();
+    let hex: String = digest[..OUTPUT_NAME_LEN].iter().map(|b| format!("{:02x}", b)).collect();
+    format!("{}.enc", hex)
+}
+
+pub fn content_hash(data: &[u8]) -> String {
+    Sha256::digest(data).iter().map(|b| format!("{:02x}", b)).collect()
+}
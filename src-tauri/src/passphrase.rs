@@ -0,0 +1,103 @@
+use aes_gcm::aead::{Aead, AeadCore, OsRng};
+use aes_gcm::{Aes256Gcm, Key, KeyInit, Nonce};
+use argon2::{Algorithm, Argon2, Params, Version};
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
+use rand::RngCore;
+use serde::{Deserialize, Serialize};
+use std::collections::HashMap;
+
+use crate::envelope::Stanza;
+
+const SALT_LEN: usize = 16;
+
+// 헤더에 기록하는 Argon2id 비용. 받는 쪽은 헤더의 값으로 같은 키를 다시 만듦
+#[derive(Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct KdfParams {
+    pub memory_kib: u32,
+    pub iterations: u32,
+    pub parallelism: u32,
+}
+
+impl KdfParams {
+    // OWASP 권장 최소값보다 조금 높게 (64 MiB, 3회)
+    pub const DEFAULT: KdfParams = KdfParams { memory_kib: 65536, iterations: 3, parallelism: 1 };
+
+    // 조작된 헤더로 메모리/시간을 과도하게 쓰게 만들지 못하도록 상한을 둠
+    fn check(&self) -> Result<(), String> {
+        if self.memory_kib > 1024 * 1024 || self.iterations > 16 || self.parallelism > 8 {
+            return Err("Invalid file: password parameters are out of range".into());
+        }
+        Ok(())
+    }
+}
+
+/******************* 비밀번호에서 파일 키를 암호화할 키를 유도 ******************/
+// 같은 작업에서 암호화하는 파일은 솔트를 공유하므로 Argon2 는 작업마다 한 번만 실행됨
+// 파일 키 자체는 파일마다 무작위로 만들어지므로 파일끼리 키를 공유하지는 않음
+pub struct PasswordKey {
+    salt: [u8; SALT_LEN],
+    params: KdfParams,
+    key: Key<Aes256Gcm>,
+}
+
+impl PasswordKey {
+    pub fn new(password: &str) -> Result<Self, String> {
+        let mut salt = [0u8; SALT_LEN];
+        rand::rng().fill_bytes(&mut salt);
+        let params = KdfParams::DEFAULT;
+        let key = derive(password, &salt, &params)?;
+        Ok(PasswordKey { salt, params, key })
+    }
+
+    pub fn wrap_file_key(&self, file_key: &[u8]) -> Result<Stanza, String> {
+        let cipher = Aes256Gcm::new(&self.key);
+        let nonce = Aes256Gcm::generate_nonce(&mut OsRng);
+        let wrapped_key = cipher.encrypt(&nonce, file_key).map_err(|e| e.to_string())?;
+        Ok(Stanza::Password {
+            salt: BASE64.encode(self.salt),
+            params: self.params,
+            nonce: BASE64.encode(nonce),
+            wrapped_key: BASE64.encode(wrapped_key),
+        })
+    }
+}
+
+// 복호화할 때 같은 솔트/비용의 파일은 유도한 키를 다시 사용
+pub type KeyCache = HashMap<(Vec<u8>, KdfParams), Key<Aes256Gcm>>;
+
+// 비밀번호 항목이 아니면 Ok(None), 비밀번호가 틀리면 Err
+pub fn unwrap_file_key(stanza: &Stanza, password: &str, cache: &mut KeyCache) -> Result<Option<Vec<u8>>, String> {
+    let Stanza::Password { salt, params, nonce, wrapped_key } = stanza else { return Ok(None) };
+    let invalid = || "Invalid file: damaged password header".to_string();
+    let salt = BASE64.decode(salt).map_err(|_| invalid())?;
+    let nonce = BASE64.decode(nonce).map_err(|_| invalid())?;
+    let wrapped_key = BASE64.decode(wrapped_key).map_err(|_| invalid())?;
+    if nonce.len() != 12 { return Err(invalid()); }
+    params.check()?;
+
+    let key = match cache.get(&(salt.clone(), *params)) {
+        Some(key) => *key,
+        None => {
+            let key = derive(password, &salt, params)?;
+            cache.insert((salt, *params), key);
+            key
+        }
+    };
+    let cipher = Aes256Gcm::new(&key);
+    cipher
+        .decrypt(Nonce::from_slice(&nonce), wrapped_key.as_slice())
+        .map(Some)
+        .map_err(|_| "Decryption failed: wrong password or damaged file".to_string())
+}
+
+fn derive(password: &str, salt: &[u8], params: &KdfParams) -> Result<Key<Aes256Gcm>, String> {
+    let argon_params = Params::new(params.memory_kib, params.iterations, params.parallelism, Some(32))
+        .map_err(|e| format!("Invalid file: {}", e))?;
+    let mut key = [0u8; 32];
+    Argon2::new(Algorithm::Argon2id, Version::V0x13, argon_params)
+        .hash_password_into(password.as_bytes(), salt, &mut key)
+        .map_err(|e| e.to_string())?;
+    Ok(Key::<Aes256Gcm>::from(key))
+}
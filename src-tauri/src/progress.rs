@@ -0,0 +1,130 @@
+use std::fs::{self, File};
+use std::io::{Read, Write};
+use std::path::Path;
+use std::time::{Duration, Instant};
+use uuid::Uuid;
+
+const IO_CHUNK_SIZE: usize = 1024 * 1024;
+// 진행 상황을 보내는 최소 간격 (작은 파일이 많아도 IPC 로 너무 자주 보내지 않도록)
+const EMIT_INTERVAL: Duration = Duration::from_millis(100);
+// 처리 속도가 안정되기 전에는 남은 시간을 계산하지 않음
+const MIN_ETA_ELAPSED: Duration = Duration::from_millis(500);
+
+// 프론트엔드로 보낼 바이트 단위 진행 상황
+#[derive(Clone, Copy, Default)]
+pub struct ProgressStats {
+    pub processed_bytes: u64,
+    pub total_bytes: u64,
+    pub progress: f64, // 0.0 ~ 1.0
+    pub bytes_per_second: f64,
+    pub eta_seconds: Option<f64>,
+}
+
+/******************* 작업 전체의 처리 바이트 수, 속도, 남은 시간 계산 ******************/
+// 끝난 파일의 크기와 지금 처리 중인 파일 안에서의 위치를 따로 저장해서,
+// 큰 파일 하나를 처리하는 동안에도 진행률이 올라가도록 함
+pub struct ProgressTracker {
+    total_bytes: u64,
+    completed_bytes: u64,
+    current_bytes: u64,
+    started: Instant,
+    last_emit: Option<Instant>,
+}
+
+impl ProgressTracker {
+    pub fn new(total_bytes: u64) -> Self {
+        ProgressTracker { total_bytes, completed_bytes: 0, current_bytes: 0, started: Instant::now(), last_emit: None }
+    }
+
+    // 빈 공간 삭제처럼 작업 중에 전체 크기가 바뀌는 경우
+    pub fn set_total(&mut self, total_bytes: u64) {
+        self.total_bytes = total_bytes;
+    }
+
+    // 지금 처리 중인 파일에서 끝난 바이트 수 (파일 크기 기준)
+    pub fn set_current(&mut self, bytes: u64) {
+        self.current_bytes = bytes;
+    }
+
+    // 파일 하나가 끝나면 (성공/실패와 관계없이) 그 크기만큼 전체 진행에 반영
+    pub fn finish_file(&mut self, size: u64) {
+        self.completed_bytes += size;
+        self.current_bytes = 0;
+    }
+
+    // 마지막으로 보낸 뒤 EMIT_INTERVAL 이 지났을 때만 true
+    pub fn should_emit(&mut self) -> bool {
+        let now = Instant::now();
+        match self.last_emit {
+            Some(last) if now.duration_since(last) < EMIT_INTERVAL => false,
+            _ => {
+                self.last_emit = Some(now);
+                true
+            }
+        }
+    }
+
+    pub fn stats(&self) -> ProgressStats {
+        let processed_bytes = (self.completed_bytes + self.current_bytes).min(self.total_bytes);
+        let elapsed = self.started.elapsed();
+        let bytes_per_second =
+            if elapsed.as_secs_f64() > 0.0 { processed_bytes as f64 / elapsed.as_secs_f64() } else { 0.0 };
+        let eta_seconds = (elapsed >= MIN_ETA_ELAPSED && bytes_per_second > 0.0)
+            .then(|| (self.total_bytes - processed_bytes) as f64 / bytes_per_second);
+        ProgressStats {
+            processed_bytes,
+            total_bytes: self.total_bytes,
+            progress: if self.total_bytes > 0 { processed_bytes as f64 / self.total_bytes as f64 } else { 0.0 },
+            bytes_per_second,
+            eta_seconds,
+        }
+    }
+}
+
+// done/total 비율을 size 기준의 바이트 수로 환산 (큰 파일에서 곱셈이 넘치지 않도록 u128 로 계산)
+pub fn scale(done: u64, total: u64, size: u64) -> u64 {
+    if total == 0 {
+        return 0;
+    }
+    (done.min(total) as u128 * size as u128 / total as u128) as u64
+}
+
+/******************* 파일을 1MB 단위로 읽으면서 읽은 바이트 수를 전달 ******************/
+pub fn read_with_progress(path: &Path, on_progress: &mut dyn FnMut(u64)) -> Result<Vec<u8>, String> {
+    let mut file = File::open(path).map_err(|e| e.to_string())?;
+    let size_hint = file.metadata().map(|m| m.len() as usize).unwrap_or(0);
+    let mut data = Vec::with_capacity(size_hint);
+    let mut buffer = vec![0u8; IO_CHUNK_SIZE];
+    loop {
+        let read = file.read(&mut buffer).map_err(|e| e.to_string())?;
+        if read == 0 {
+            return Ok(data);
+        }
+        data.extend_from_slice(&buffer[..read]);
+        on_progress(data.len() as u64);
+    }
+}
+
+/******************* 데이터를 1MB 단위로 기록하면서 기록한 바이트 수를 전달 ******************/
+// 같은 폴더의 임시 파일에 모두 기록한 뒤 이름을 바꾸므로, 중간에 실패해도 일부만 기록된 파일이 남지 않음
+pub fn write_with_progress(path: &Path, data: &[u8], on_progress: &mut dyn FnMut(u64)) -> Result<(), String> {
+    let file_name = path.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+    let temp_path = path.with_file_name(format!(".{}.{}.part", file_name, Uuid::new_v4()));
+
+    let result = (|| {
+        let mut file = File::create(&temp_path).map_err(|e| e.to_string())?;
+        let mut written = 0u64;
+        for chunk in data.chunks(IO_CHUNK_SIZE) {
+            file.write_all(chunk).map_err(|e| e.to_string())?;
+            written += chunk.len() as u64;
+            on_progress(written);
+        }
+        file.sync_all().map_err(|e| e.to_string())?;
+        fs::rename(&temp_path, path).map_err(|e| e.to_string())
+    })();
+
+    if result.is_err() {
+        let _ = fs::remove_file(&temp_path);
+    }
+    result
+}
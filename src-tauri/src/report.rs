@@ -0,0 +1,312 @@
+use serde::Serialize;
+use std::fs;
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::storage::WipeConfidence;
+use crate::EachFile;
+
+// 항목 하나의 최종 상태
+#[derive(Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum EntryStatus {
+    Succeeded,
+    Failed,
+    // 트랜잭션 모드에서 처리에는 성공했지만 전체가 완료되지 않아 반영하지 않은 항목
+    RolledBack,
+    NotStarted,
+}
+
+// 작업 전체의 최종 상태
+#[derive(Clone, Copy, PartialEq, Eq, Serialize, serde::Deserialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum JobStatus {
+    Completed,
+    Cancelled,
+    RolledBack,
+}
+
+#[derive(Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ReportEntry {
+    pub input_path: String,
+    pub output_path: Option<String>,
+    pub size: u64,
+    pub duration_ms: u64,
+    pub status: EntryStatus,
+    pub error_code: Option<String>,
+    pub error_message: Option<String>,
+    pub scheme: Option<String>,
+    pub confidence: Option<WipeConfidence>,
+    pub note: Option<String>,
+}
+
+impl ReportEntry {
+    pub fn from_file(file: &EachFile, status: EntryStatus) -> Self {
+        ReportEntry {
+            input_path: file.path.clone(),
+            output_path: file.output.clone(),
+            size: file.size,
+            duration_ms: file.duration_ms,
+            status,
+            error_code: file.error_code.clone(),
+            error_message: file.error.clone(),
+            scheme: file.scheme.clone(),
+            confidence: file.confidence,
+            note: file.note.clone(),
+        }
+    }
+
+    pub fn not_started(path: String) -> Self {
+        ReportEntry {
+            input_path: path,
+            output_path: None,
+            size: 0,
+            duration_ms: 0,
+            status: EntryStatus::NotStarted,
+            error_code: None,
+            error_message: None,
+            scheme: None,
+            confidence: None,
+            note: None,
+        }
+    }
+}
+
+/******************* 끝난 작업 하나의 처리 기록 ******************/
+#[derive(Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct JobReport {
+    pub job_id: String,
+    pub operation: String,
+    pub status: JobStatus,
+    pub started_at: String,
+    pub finished_at: String,
+    pub duration_ms: u64,
+    pub reason: Option<String>, // 취소/되돌리기 이유
+    pub problems: Vec<String>,  // 작업 결과와 별개로 생긴 문제 (진행 상황 전송이 끊김, 감사 로그를 쓰지 못함 등)
+    pub entries: Vec<ReportEntry>,
+}
+
+// 보고서 파일 형식
+#[derive(Clone, Copy, serde::Deserialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ReportFormat {
+    Json,
+    Csv,
+}
+
+impl JobReport {
+    pub fn write(&self, path: &Path, format: ReportFormat) -> Result<(), String> {
+        let contents = match format {
+            ReportFormat::Json => serde_json::to_string_pretty(self).map_err(|e| e.to_string())?,
+            ReportFormat::Csv => self.to_csv(),
+        };
+        fs::write(path, contents).map_err(|e| e.to_string())
+    }
+
+    // 항목마다 한 줄, 작업 정보(ID, 종류, 시각)는 모든 줄에 반복해서 넣어 다른 보고서와 합쳐도 구분되도록 함
+    fn to_csv(&self) -> String {
+        let mut csv = String::from(
+            "job_id,operation,job_status,started_at,finished_at,input_path,output_path,size,duration_ms,status,error_code,error_message,scheme,confidence,note\n",
+        );
+        for entry in &self.entries {
+            let fields = [
+                self.job_id.clone(),
+                self.operation.clone(),
+                enum_name(&self.status),
+                self.started_at.clone(),
+                self.finished_at.clone(),
+                entry.input_path.clone(),
+                entry.output_path.clone().unwrap_or_default(),
+                entry.size.to_string(),
+                entry.duration_ms.to_string(),
+                enum_name(&entry.status),
+                entry.error_code.clone().unwrap_or_default(),
+                entry.error_message.clone().unwrap_or_default(),
+                entry.scheme.clone().unwrap_or_default(),
+                entry.confidence.as_ref().map(enum_name).unwrap_or_default(),
+                entry.note.clone().unwrap_or_default(),
+            ];
+            let line: Vec<String> = fields.iter().map(|field| csv_field(field)).collect();
+            csv.push_str(&line.join(","));
+            csv.push('\n');
+        }
+        csv
+    }
+}
+
+// serde 에서 쓰는 이름 그대로 (예: ROLLED_BACK)
+fn enum_name<T: Serialize>(value: &T) -> String {
+    serde_json::to_value(value).ok().and_then(|v| v.as_str().map(str::to_string)).unwrap_or_default()
+}
+
+// 쉼표, 따옴표, 줄바꿈이 있으면 따옴표로 감싸고 안의 따옴표는 두 번 씀 (RFC 4180)
+// 스프레드시트에서 수식으로 실행될 값 앞에만 ' 를 붙임 (-backup/file.txt 같은 경로는 그대로 둠)
+fn csv_field(value: &str) -> String {
+    let value = if is_formula(value) { format!("'{}", value) } else { value.to_string() };
+    if value.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value
+    }
+}
+
+// = 와 @ 로 시작하거나 탭/CR 로 시작하면 항상 수식으로 봄
+// + 와 - 는 숫자나 경로에도 흔히 쓰이므로, 함수 호출이나 다른 시트/프로그램 참조(( ! | =)가 뒤따를 때만 수식으로 봄
+fn is_formula(value: &str) -> bool {
+    match value.chars().next() {
+        Some('=' | '@' | '\t' | '\r') => true,
+        Some('+' | '-') => value[1..].contains(['(', '!', '|', '=']),
+        _ => false,
+    }
+}
+
+/******************* 오류 메시지를 보고서용 오류 코드로 분류 ******************/
+// 오류는 문자열로 전달되므로 메시지 내용으로 판단하고, 해당하지 않으면 IO_ERROR
+// 취소는 다른 메시지에 덧붙기도 하므로 가장 먼저 확인하고, 이 앱이 만드는 메시지는 시작(경로가 앞에 오는 것은 끝) 부분으로 구분함
+// 그래야 메시지 안의 파일 이름이나 경로에 "not found" 같은 단어가 있어도 잘못 분류하지 않음
+// 운영체제 오류(io::Error)는 "Failed to ...: " 뒤에 붙어 오므로 포함 여부로 판단
+pub fn error_code(message: &str) -> &'static str {
+    let lower = message.to_lowercase();
+    if lower.contains("operation cancelled") {
+        return "CANCELLED";
+    }
+    let own_messages: [(&str, &'static str); 6] = [
+        ("Decryption failed", "DECRYPTION_FAILED"),
+        ("Invalid file", "INVALID_FILE"),
+        ("Verification failed", "VERIFY_FAILED"),
+        ("Skipped", "SKIPPED"),
+        ("Refused", "SKIPPED"),
+        ("More than one file would be written to", "ALREADY_EXISTS"),
+    ];
+    if let Some((_, code)) = own_messages.iter().find(|(prefix, _)| message.starts_with(prefix)) {
+        return code;
+    }
+    if message.ends_with(" already exists") {
+        return "ALREADY_EXISTS";
+    }
+    let os_errors: [(&[&str], &'static str); 5] = [
+        (&["no such file", "not found", "cannot find"], "NOT_FOUND"),
+        (&["permission denied", "access is denied", "operation not permitted"], "PERMISSION_DENIED"),
+        (&["no space left", "disk full", "not enough space"], "STORAGE_FULL"),
+        (&["file exists", "already exists"], "ALREADY_EXISTS"),
+        (&["not supported"], "UNSUPPORTED"),
+    ];
+    os_errors
+        .iter()
+        .find(|(patterns, _)| patterns.iter().any(|p| lower.contains(p)))
+        .map(|(_, code)| *code)
+        .unwrap_or("IO_ERROR")
+}
+
+/******************* 현재 시각을 UTC ISO 8601 문자열로 ******************/
+pub fn now_utc() -> String {
+    let elapsed = SystemTime::now().duration_since(UNIX_EPOCH).unwrap_or_default();
+    format_utc(elapsed.as_secs(), elapsed.subsec_millis())
+}
+
+// 유닉스 시간을 "2024-01-31T12:34:56.789Z" 형태로 변환 (일 수 → 연월일 변환은 그레고리력 기준)
+pub fn format_utc(unix_seconds: u64, millis: u32) -> String {
+    let days = (unix_seconds / 86_400) as i64;
+    let seconds_of_day = unix_seconds % 86_400;
+
+    let z = days + 719_468;
+    let era = z.div_euclid(146_097);
+    let day_of_era = z.rem_euclid(146_097);
+    let year_of_era = (day_of_era - day_of_era / 1_460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let mp = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * mp + 2) / 5 + 1;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = year_of_era + era * 400 + i64::from(month <= 2);
+
+    format!(
+        "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}.{:03}Z",
+        year,
+        month,
+        day,
+        seconds_of_day / 3_600,
+        seconds_of_day % 3_600 / 60,
+        seconds_of_day % 60,
+        millis
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn csv_field_quotes_as_rfc_4180() {
+        assert_eq!(csv_field("report.pdf"), "report.pdf");
+        assert_eq!(csv_field("a,b"), "\"a,b\"");
+        assert_eq!(csv_field("say \"hi\""), "\"say \"\"hi\"\"\"");
+        assert_eq!(csv_field("line\nbreak"), "\"line\nbreak\"");
+        assert_eq!(csv_field("line\rbreak"), "\"line\rbreak\"");
+        assert_eq!(csv_field(""), "");
+    }
+
+    #[test]
+    fn csv_field_escapes_only_formulas() {
+        assert_eq!(csv_field("=1+1"), "'=1+1");
+        assert_eq!(csv_field("@SUM(A1:A2)"), "'@SUM(A1:A2)");
+        assert_eq!(csv_field("+SUM(A1:A2)"), "'+SUM(A1:A2)");
+        assert_eq!(csv_field("-2+3+cmd|' /C calc'!A0"), "'-2+3+cmd|' /C calc'!A0");
+        assert_eq!(csv_field("\t=1+1"), "'\t=1+1");
+        // 수식이면서 따옴표가 있으면 ' 를 붙인 뒤 감쌈
+        assert_eq!(csv_field("=HYPERLINK(\"http://x\",\"y\")"), "\"'=HYPERLINK(\"\"http://x\"\",\"\"y\"\")\"");
+
+        assert_eq!(csv_field("-backup/file.txt"), "-backup/file.txt");
+        assert_eq!(csv_field("-5"), "-5");
+        assert_eq!(csv_field("+82 10 1234 5678"), "+82 10 1234 5678");
+        assert_eq!(csv_field("a=b"), "a=b");
+    }
+
+    #[test]
+    fn format_utc_handles_leap_days_and_centuries() {
+        assert_eq!(format_utc(0, 0), "1970-01-01T00:00:00.000Z");
+        assert_eq!(format_utc(951_782_400, 0), "2000-02-29T00:00:00.000Z");
+        assert_eq!(format_utc(951_868_799, 999), "2000-02-29T23:59:59.999Z");
+        // 2100 년은 윤년이 아니므로 2월 28일 다음 날이 3월 1일
+        assert_eq!(format_utc(4_107_456_000 + 86_400, 0), "2100-03-01T00:00:00.000Z");
+        assert_eq!(format_utc(4_107_542_400, 5), "2100-03-01T00:00:00.005Z");
+    }
+
+    // main.rs, wipe.rs, staging.rs 등에서 실제로 만드는 메시지와 운영체제 오류 메시지
+    #[test]
+    fn error_codes_for_real_messages() {
+        let cases = [
+            ("Operation cancelled.", "CANCELLED"),
+            ("Decryption failed: this file was encrypted with a different vault key", "DECRYPTION_FAILED"),
+            ("Decryption failed: no identity in this vault is a recipient of this file", "DECRYPTION_FAILED"),
+            ("Decryption failed: metadata corrupt", "DECRYPTION_FAILED"),
+            ("Invalid file: header has no recipients", "INVALID_FILE"),
+            ("Invalid file: unsafe original file name \"../not found.txt\"", "INVALID_FILE"),
+            ("Verification failed at offset 4096", "VERIFY_FAILED"),
+            ("Refused: not a regular file", "SKIPPED"),
+            ("Skipped: device file", "SKIPPED"),
+            ("Directory not empty: some entries were skipped or failed", "IO_ERROR"),
+            ("/out/not found/report.pdf already exists", "ALREADY_EXISTS"),
+            ("More than one file would be written to /out/report.pdf", "ALREADY_EXISTS"),
+            ("Failed to move /out/report.pdf into place: File exists (os error 17)", "ALREADY_EXISTS"),
+            ("No such file or directory (os error 2)", "NOT_FOUND"),
+            ("The system cannot find the file specified. (os error 2)", "NOT_FOUND"),
+            ("Permission denied (os error 13)", "PERMISSION_DENIED"),
+            ("Access is denied. (os error 5)", "PERMISSION_DENIED"),
+            ("No space left on device (os error 28)", "STORAGE_FULL"),
+            ("There is not enough space on the disk. (os error 112)", "STORAGE_FULL"),
+            ("Operation not supported (os error 95)", "UNSUPPORTED"),
+            ("Vault is locked", "IO_ERROR"),
+        ];
+        for (message, code) in cases {
+            assert_eq!(error_code(message), code, "{}", message);
+        }
+    }
+
+    #[test]
+    fn cancellation_takes_precedence_over_other_codes() {
+        assert_eq!(error_code("Operation cancelled. Output not found"), "CANCELLED");
+        assert_eq!(error_code("report.pdf not found: Operation cancelled."), "CANCELLED");
+    }
+}
@@ -0,0 +1,190 @@
+use aes_gcm::aead::{Aead, AeadCore, OsRng, Payload};
+use aes_gcm::{Aes256Gcm, Key, KeyInit, Nonce};
+use base64::engine::general_purpose::STANDARD as BASE64;
+use base64::Engine;
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use walkdir::WalkDir;
+
+use crate::data_key;
+use crate::envelope::{self, FileHeader, Stanza};
+use crate::mirror;
+use crate::progress;
+use crate::report;
+use crate::subkey::{self, Purpose};
+use crate::vault_file;
+
+const KEYRING_AAD: &[u8] = b"securevault keyring";
+const NONCE_LEN: usize = 12;
+
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+struct PreviousKey {
+    key_id: String,
+    key: String, // base64
+}
+
+/******************* 마스터 키 교체 중 남겨 두는 이전 키 (vault 파일 옆의 <이름>.keyring) ******************/
+// 키를 바꾼 뒤에도 아직 새 키로 옮기지 않은 파일을 열 수 있도록, 이전 마스터 키를 현재 마스터 키로 암호화해서 보관함
+// 선택한 폴더의 파일을 모두 옮기면 이 파일을 덮어쓴 뒤 삭제해서 이전 키를 폐기함
+// [논스(12)] + [암호화된 JSON]
+#[derive(Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct KeyRing {
+    previous: Vec<PreviousKey>,
+    folders: Vec<String>, // 새 키로 옮길 폴더 (작업을 이어서 할 때 다시 사용)
+    rotated_at: String,
+}
+
+// 프론트엔드에 보여줄 교체 진행 상황 (키 자체는 보내지 않음)
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RotationStatus {
+    pub current_key_id: String,
+    pub previous_key_ids: Vec<String>,
+    pub folders: Vec<String>,
+    pub rotated_at: Option<String>,
+}
+
+impl KeyRing {
+    pub fn new(previous_key: &Key<Aes256Gcm>, folders: Vec<String>) -> Self {
+        KeyRing {
+            previous: vec![PreviousKey { key_id: data_key::key_id(previous_key), key: BASE64.encode(previous_key) }],
+            folders,
+            rotated_at: report::now_utc(),
+        }
+    }
+
+    // 파일이 없으면 교체 중이 아님
+    pub fn load(vault_path: &Path, vault_key: &Key<Aes256Gcm>) -> Result<Self, String> {
+        let path = keyring_path(vault_path);
+        if !path.exists() {
+            return Ok(KeyRing::default());
+        }
+        let data = fs::read(&path).map_err(|e| e.to_string())?;
+        if data.len() < NONCE_LEN { return Err("Previous key list is damaged".into()); }
+        let cipher = Aes256Gcm::new(&subkey::derive(vault_key, Purpose::KeyRing));
+        let payload = Payload { msg: &data[NONCE_LEN..], aad: KEYRING_AAD };
+        let json = cipher.decrypt(Nonce::from_slice(&data[..NONCE_LEN]), payload).map_err(|_| "Previous key list is damaged".to_string())?;
+        serde_json::from_slice(&json).map_err(|e| format!("Previous key list is damaged: {}", e))
+    }
+
+    pub fn save(&self, vault_path: &Path, vault_key: &Key<Aes256Gcm>) -> Result<(), String> {
+        let json = serde_json::to_vec(self).map_err(|e| e.to_string())?;
+        let cipher = Aes256Gcm::new(&subkey::derive(vault_key, Purpose::KeyRing));
+        let nonce = Aes256Gcm::generate_nonce(&mut OsRng);
+        let encrypted = cipher.encrypt(&nonce, Payload { msg: &json, aad: KEYRING_AAD }).map_err(|e| e.to_string())?;
+        let mut data = nonce.to_vec();
+        data.extend_from_slice(&encrypted);
+        progress::write_with_progress(&keyring_path(vault_path), &data, &mut |_| {})
+    }
+
+    pub fn keys(&self) -> Vec<Key<Aes256Gcm>> {
+        self.previous
+            .iter()
+            .filter_map(|p| BASE64.decode(&p.key).ok())
+            .filter(|bytes| bytes.len() == 32)
+            .map(|bytes| *Key::<Aes256Gcm>::from_slice(&bytes))
+            .collect()
+    }
+
+    pub fn folders(&self) -> &[String] {
+        &self.folders
+    }
+
+    pub fn status(&self, vault_key: &Key<Aes256Gcm>) -> RotationStatus {
+        RotationStatus {
+            current_key_id: data_key::key_id(vault_key),
+            previous_key_ids: self.previous.iter().map(|p| p.key_id.clone()).collect(),
+            folders: self.folders.clone(),
+            rotated_at: (!self.previous.is_empty()).then(|| self.rotated_at.clone()),
+        }
+    }
+}
+
+pub fn keyring_path(vault_path: &Path) -> PathBuf {
+    vault_file::side_path(vault_path, "keyring")
+}
+
+/******************* 새 키로 옮길 .enc 파일과 폴더 미러 목록 ******************/
+// 읽을 수 없는 항목은 (경로, 이유) 로 따로 반환
+pub fn collect_files(folders: &[String]) -> (Vec<PathBuf>, Vec<(String, String)>) {
+    let mut files = Vec::new();
+    let mut errors = Vec::new();
+    for root in folders {
+        for entry in WalkDir::new(root).follow_root_links(false) {
+            match entry {
+                Ok(entry) => {
+                    let is_enc = entry.path().extension().is_some_and(|ext| ext.eq_ignore_ascii_case("enc"));
+                    if entry.file_type().is_file() && (is_enc || mirror::is_manifest(entry.path())) {
+                        files.push(entry.into_path());
+                    }
+                }
+                Err(e) => {
+                    let path = e.path().map(|p| p.to_string_lossy().to_string()).unwrap_or_else(|| root.clone());
+                    errors.push((path, e.to_string()));
+                }
+            }
+        }
+    }
+    (files, errors)
+}
+
+/******************* 파일 하나를 현재 마스터 키로 옮김 ******************/
+// 파일 키가 있는 파일은 헤더의 vault 항목만 다시 암호화하고 본문은 그대로 둠
+// 파일 키 없이 마스터 키로 바로 암호화된 파일(이전 형식)은 파일 키를 새로 만들어 본문까지 다시 암호화함
+// 이미 옮겼거나 이 vault 의 키로 암호화되지 않은 파일은 건드리지 않으므로, 중단된 작업을 처음부터 다시 실행해도 됨
+// 폴더 미러 목록은 현재 키로 다시 저장함. 결과로 보고서에 남길 처리 내용을 반환
+pub fn rewrap_file(path: &Path, vault_key: &Key<Aes256Gcm>, previous_keys: &[Key<Aes256Gcm>]) -> Result<String, String> {
+    if mirror::is_manifest(path) {
+        return mirror::rekey_manifest(path, vault_key, previous_keys);
+    }
+    let data = fs::read(path).map_err(|e| e.to_string())?;
+    let rewritten = match envelope::read_header(&data) {
+        Some((mut header, body)) => {
+            header.validate()?;
+            let mut changed = false;
+            for stanza in header.recipients.iter_mut() {
+                let Stanza::Vault { key_id, .. } = stanza else { continue };
+                let Some(previous_key) = previous_keys.iter().find(|k| data_key::key_id(k) == *key_id) else { continue };
+                let file_key = data_key::unwrap(stanza, previous_key).ok_or("Decryption failed: damaged key in file header")?;
+                *stanza = data_key::wrap(Key::<Aes256Gcm>::from_slice(&file_key), vault_key)?;
+                changed = true;
+            }
+            if !changed {
+                return Ok(skipped_note(&header, vault_key));
+            }
+            let mut rewritten = envelope::write_header(&header)?;
+            rewritten.extend_from_slice(body);
+            rewritten
+        }
+        None => {
+            let (header, body) = (FileHeader::default(), data.as_slice());
+            let Some((metadata_bytes, content)) = previous_keys.iter().find_map(|k| envelope::open_body(&header, k, body).ok()) else {
+                return Ok(if envelope::open_body(&header, vault_key, body).is_ok() {
+                    "Already uses the current key".to_string()
+                } else {
+                    "Skipped: not encrypted with this vault's keys".to_string()
+                });
+            };
+            let file_key = data_key::generate();
+            let header = FileHeader::new(header.cipher, vec![data_key::wrap(&file_key, vault_key)?]);
+            let mut rewritten = envelope::write_header(&header)?;
+            rewritten.extend_from_slice(&envelope::seal_body(&header, &file_key, &metadata_bytes, &content)?);
+            rewritten
+        }
+    };
+    progress::write_with_progress(path, &rewritten, &mut |_| {})?;
+    Ok("Moved to the current key".to_string())
+}
+
+fn skipped_note(header: &FileHeader, vault_key: &Key<Aes256Gcm>) -> String {
+    let current_key_id = data_key::key_id(vault_key);
+    let uses_current = header.recipients.iter().any(|s| matches!(s, Stanza::Vault { key_id, .. } if *key_id == current_key_id));
+    if uses_current {
+        "Already uses the current key".to_string()
+    } else {
+        "Skipped: not encrypted with this vault's keys".to_string()
+    }
+}
@@ -0,0 +1,331 @@
+use glob::{MatchOptions, Pattern};
+use serde::{Deserialize, Serialize};
+use std::cell::RefCell;
+use std::path::Path;
+use walkdir::{DirEntry, WalkDir};
+
+use crate::wipe::{self, HardlinkPolicy, TargetKind};
+
+// 심볼릭 링크를 만났을 때의 처리 방식
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum SymlinkPolicy {
+    // 링크는 목록에 넣지 않고 건너뛴 항목으로 보고 (보안 삭제와 같은 기준)
+    #[default]
+    Skip,
+    // 링크가 가리키는 파일/폴더를 따라가서 포함 (순환 링크는 오류로 보고)
+    Follow,
+}
+
+// 폴더 탐색 옵션. 프론트엔드에서 생략하면 기존처럼 모든 일반 파일을 가져옴
+#[derive(Clone, Debug, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct ScanOptions {
+    // 비어 있지 않으면 하나 이상과 일치하는 파일만 포함
+    pub include: Vec<String>,
+    // 일치하는 파일은 제외하고, 일치하는 폴더는 안으로 들어가지 않음
+    pub exclude: Vec<String>,
+    pub include_hidden: bool,
+    pub min_size: Option<u64>,
+    pub max_size: Option<u64>,
+    pub max_depth: Option<usize>,
+    pub symlinks: SymlinkPolicy,
+    // 하드 링크가 걸린 파일: Refuse 면 건너뛰고 Warn 이면 포함하되 알림을 남김 (생략 시 보안 삭제와 같은 Refuse)
+    pub hardlinks: HardlinkPolicy,
+}
+
+impl Default for ScanOptions {
+    fn default() -> Self {
+        ScanOptions {
+            include: Vec::new(),
+            exclude: Vec::new(),
+            include_hidden: true,
+            min_size: None,
+            max_size: None,
+            max_depth: None,
+            symlinks: SymlinkPolicy::Skip,
+            hardlinks: HardlinkPolicy::Refuse,
+        }
+    }
+}
+
+// 목록에서 빠진 항목과 그 이유
+#[derive(Clone, Serialize)]
+pub struct ScanEntry {
+    pub path: String,
+    pub reason: String,
+}
+
+// 탐색 중 발견한 항목 하나
+// File 의 note 는 목록에 넣었지만 알려야 할 점 (하드 링크 등)
+pub enum ScanItem {
+    File { path: String, size: u64, note: Option<String> },
+    Skipped(ScanEntry),
+    Error(ScanEntry),
+}
+
+// 폴더 탐색 결과 전체
+#[derive(Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScanResult {
+    pub files: Vec<String>,
+    pub total_bytes: u64,
+    pub skipped: Vec<ScanEntry>,
+    pub errors: Vec<ScanEntry>,
+    pub notes: Vec<ScanEntry>,
+}
+
+// 탐색 중 일정 개수/시간마다 프론트엔드로 보내는 중간 결과
+// 목록(files/skipped/errors)은 지난 전송 이후 새로 찾은 항목만, 개수와 크기는 지금까지의 누적값
+#[derive(Clone, Default, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ScanBatch {
+    pub status: String,
+    pub files: Vec<String>,
+    pub skipped: Vec<ScanEntry>,
+    pub errors: Vec<ScanEntry>,
+    pub notes: Vec<ScanEntry>,
+    pub file_count: usize,
+    pub skipped_count: usize,
+    pub error_count: usize,
+    pub note_count: usize,
+    pub total_bytes: u64,
+}
+
+impl ScanBatch {
+    pub fn push(&mut self, item: ScanItem) {
+        match item {
+            ScanItem::File { path, size, note } => {
+                if let Some(reason) = note {
+                    self.notes.push(ScanEntry { path: path.clone(), reason });
+                    self.note_count += 1;
+                }
+                self.files.push(path);
+                self.file_count += 1;
+                self.total_bytes += size;
+            }
+            ScanItem::Skipped(entry) => {
+                self.skipped.push(entry);
+                self.skipped_count += 1;
+            }
+            ScanItem::Error(entry) => {
+                self.errors.push(entry);
+                self.error_count += 1;
+            }
+        }
+    }
+
+    pub fn pending_len(&self) -> usize {
+        self.files.len() + self.skipped.len() + self.errors.len() + self.notes.len()
+    }
+
+    // 보낼 중간 결과를 꺼내고, 목록은 비우되 누적값은 유지
+    pub fn take(&mut self, status: &str) -> ScanBatch {
+        ScanBatch {
+            status: status.to_string(),
+            files: std::mem::take(&mut self.files),
+            skipped: std::mem::take(&mut self.skipped),
+            errors: std::mem::take(&mut self.errors),
+            notes: std::mem::take(&mut self.notes),
+            ..self.clone()
+        }
+    }
+}
+
+// 글롭 패턴. '/' 가 없으면 이름에만, 있으면 탐색 시작 폴더 기준의 상대 경로에 맞춰 봄
+struct GlobPattern {
+    pattern: Pattern,
+    match_name_only: bool,
+}
+
+impl GlobPattern {
+    fn parse(raw: &str) -> Result<Self, String> {
+        let raw = raw.trim().trim_end_matches('/');
+        let pattern = Pattern::new(raw).map_err(|e| format!("Invalid pattern '{}': {}", raw, e))?;
+        Ok(GlobPattern { pattern, match_name_only: !raw.contains('/') })
+    }
+
+    fn matches(&self, name: &str, relative_path: &str) -> bool {
+        let options = MatchOptions { require_literal_separator: true, ..MatchOptions::new() };
+        if self.match_name_only {
+            self.pattern.matches_with(name, options)
+        } else {
+            self.pattern.matches_with(relative_path, options)
+        }
+    }
+}
+
+fn parse_patterns(raw: &[String]) -> Result<Vec<GlobPattern>, String> {
+    raw.iter().filter(|p| !p.trim().is_empty()).map(|p| GlobPattern::parse(p)).collect()
+}
+
+// 숨김 파일 여부 (이름이 '.' 으로 시작하거나, Windows 에서는 숨김 속성)
+fn is_hidden(entry: &DirEntry) -> bool {
+    if entry.file_name().to_string_lossy().starts_with('.') {
+        return true;
+    }
+    #[cfg(windows)]
+    {
+        use std::os::windows::fs::MetadataExt;
+        const FILE_ATTRIBUTE_HIDDEN: u32 = 0x2;
+        if let Ok(metadata) = entry.metadata() {
+            return metadata.file_attributes() & FILE_ATTRIBUTE_HIDDEN != 0;
+        }
+    }
+    false
+}
+
+// 탐색 시작 폴더 기준 상대 경로 ('/' 구분자로 통일)
+fn relative_path(root: &Path, path: &Path) -> String {
+    let relative = path.strip_prefix(root).unwrap_or(path);
+    relative.components().map(|c| c.as_os_str().to_string_lossy()).collect::<Vec<_>>().join("/")
+}
+
+/******************* 옵션에 따라 폴더를 탐색하면서 항목을 하나씩 전달 ******************/
+// visitor 가 false 를 반환하면 탐색을 중단함
+pub fn scan_dir(root: &Path, options: &ScanOptions, visitor: &mut dyn FnMut(ScanItem) -> bool) -> Result<(), String> {
+    let include = parse_patterns(&options.include)?;
+    let exclude = parse_patterns(&options.exclude)?;
+
+    let mut walker = WalkDir::new(root).follow_links(options.symlinks == SymlinkPolicy::Follow);
+    if let Some(max_depth) = options.max_depth {
+        walker = walker.max_depth(max_depth);
+    }
+
+    let skipped: RefCell<Vec<ScanEntry>> = RefCell::new(Vec::new());
+    // 제외된 폴더는 filter_entry 에서 걸러지므로, 이유를 기록해 두었다가 visitor 에 전달
+    let mut iter = walker.into_iter().filter_entry(|entry| {
+        if entry.depth() == 0 || !entry.file_type().is_dir() {
+            return true;
+        }
+        let name = entry.file_name().to_string_lossy().to_string();
+        let reason = if !options.include_hidden && is_hidden(entry) {
+            Some("Hidden directory".to_string())
+        } else if exclude.iter().any(|p| p.matches(&name, &relative_path(root, entry.path()))) {
+            Some("Excluded directory".to_string())
+        } else {
+            None
+        };
+        match reason {
+            Some(reason) => {
+                skipped.borrow_mut().push(ScanEntry { path: entry.path().to_string_lossy().to_string(), reason });
+                false
+            }
+            None => true,
+        }
+    });
+
+    loop {
+        let next = iter.next();
+        // filter_entry 에서 모아 둔 건너뛴 폴더 먼저 전달
+        let pending: Vec<ScanEntry> = skipped.borrow_mut().drain(..).collect();
+        for entry in pending {
+            if !visitor(ScanItem::Skipped(entry)) {
+                return Ok(());
+            }
+        }
+        let item = match next {
+            None => return Ok(()),
+            Some(Err(e)) => ScanItem::Error(ScanEntry {
+                path: e.path().unwrap_or(root).to_string_lossy().to_string(),
+                reason: e.to_string(),
+            }),
+            Some(Ok(entry)) => match classify_entry(root, &entry, options, &include, &exclude) {
+                Some(item) => item,
+                None => continue,
+            },
+        };
+        if !visitor(item) {
+            return Ok(());
+        }
+    }
+}
+
+// 항목 하나를 목록에 넣을지 판단. 폴더처럼 보고할 필요가 없는 항목은 None
+fn classify_entry(
+    root: &Path,
+    entry: &DirEntry,
+    options: &ScanOptions,
+    include: &[GlobPattern],
+    exclude: &[GlobPattern],
+) -> Option<ScanItem> {
+    let path = entry.path().to_string_lossy().to_string();
+    let skip = |reason: &str| Some(ScanItem::Skipped(ScanEntry { path: path.clone(), reason: reason.to_string() }));
+
+    match wipe::classify(&entry.file_type()) {
+        TargetKind::File => {}
+        TargetKind::Dir => {
+            // 최대 깊이에 도달해서 안을 탐색하지 않은 폴더는 알려줌
+            if entry.depth() > 0 && options.max_depth == Some(entry.depth()) {
+                return skip("Maximum depth reached");
+            }
+            return None;
+        }
+        TargetKind::Symlink => return skip("Symbolic link"),
+        TargetKind::Special(kind) => return skip(&format!("Special file ({})", kind)),
+    }
+
+    let name = entry.file_name().to_string_lossy().to_string();
+    let relative = relative_path(root, entry.path());
+    if !options.include_hidden && is_hidden(entry) {
+        return skip("Hidden file");
+    }
+    if exclude.iter().any(|p| p.matches(&name, &relative)) {
+        return skip("Excluded by pattern");
+    }
+    if !include.is_empty() && !include.iter().any(|p| p.matches(&name, &relative)) {
+        return skip("Not matched by include patterns");
+    }
+
+    let metadata = match entry.metadata() {
+        Ok(metadata) => metadata,
+        Err(e) => return Some(ScanItem::Error(ScanEntry { path, reason: e.to_string() })),
+    };
+    let size = metadata.len();
+    if options.min_size.is_some_and(|min| size < min) {
+        return skip("Smaller than minimum size");
+    }
+    if options.max_size.is_some_and(|max| size > max) {
+        return skip("Larger than maximum size");
+    }
+    // 보안 삭제(wipe::wipe_target)와 같은 기준
+    let links = wipe::link_count(&metadata);
+    let note = match options.hardlinks {
+        HardlinkPolicy::Refuse if links > 1 => return skip("File has multiple hard links"),
+        HardlinkPolicy::Warn if links > 1 => Some(format!("File has {} hard links, other names remain", links)),
+        _ => None,
+    };
+    Some(ScanItem::File { path, size, note })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn scan(root: &Path, hardlinks: HardlinkPolicy) -> (Vec<String>, usize, usize) {
+        let options = ScanOptions { hardlinks, ..ScanOptions::default() };
+        let mut batch = ScanBatch::default();
+        scan_dir(root, &options, &mut |item| {
+            batch.push(item);
+            true
+        })
+        .unwrap();
+        (batch.files, batch.skipped_count, batch.note_count)
+    }
+
+    #[test]
+    fn hard_linked_files_follow_the_secure_delete_policy() {
+        let root = std::env::temp_dir().join(format!("scan-test-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&root);
+        std::fs::create_dir_all(&root).unwrap();
+        std::fs::write(root.join("a.txt"), b"a").unwrap();
+        std::fs::hard_link(root.join("a.txt"), root.join("b.txt")).unwrap();
+        std::fs::write(root.join("c.txt"), b"c").unwrap();
+
+        let (files, skipped, notes) = scan(&root, ScanOptions::default().hardlinks);
+        assert_eq!((files.len(), skipped, notes), (1, 2, 0));
+        let (files, skipped, notes) = scan(&root, HardlinkPolicy::Warn);
+        assert_eq!((files.len(), skipped, notes), (3, 0, 2));
+        let _ = std::fs::remove_dir_all(&root);
+    }
+}
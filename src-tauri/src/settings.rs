@@ -0,0 +1,31 @@
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::Path;
+
+use crate::cipher::Cipher;
+use crate::progress;
+use crate::throttle::UnlockPolicy;
+
+/******************* 앱 설정 (설정 폴더의 settings.json) ******************/
+// 파일이 없거나 읽을 수 없으면 기본값을 사용하고, 없는 항목도 기본값으로 채움
+#[derive(Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct AppSettings {
+    pub unlock_policy: UnlockPolicy,
+    // 암호화할 때 알고리즘을 따로 고르지 않으면 사용
+    pub default_cipher: Cipher,
+}
+
+impl AppSettings {
+    pub fn load(path: &Path) -> Self {
+        fs::read(path)
+            .ok()
+            .and_then(|data| serde_json::from_slice(&data).ok())
+            .unwrap_or_default()
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), String> {
+        let data = serde_json::to_vec_pretty(self).map_err(|e| e.to_string())?;
+        progress::write_with_progress(path, &data, &mut |_| {})
+    }
+}
@@ -0,0 +1,140 @@
+use std::collections::HashSet;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::AtomicBool;
+
+use crate::wipe::{self, WipeOptions};
+
+/******************* 기존 파일을 덮어쓰지 않도록 확인 ******************/
+// 트랜잭션 모드(commit)와 바로 기록하는 경우 모두 같은 규칙과 같은 오류를 사용함 (링크도 기존 파일로 봄)
+pub fn ensure_absent(path: &Path) -> Result<(), String> {
+    if fs::symlink_metadata(path).is_ok() {
+        return Err(format!("{} already exists", path.display()));
+    }
+    Ok(())
+}
+
+// 대상 폴더 아래의 하위 폴더를 만들고 새로 만든 폴더를 (위에서부터) 반환
+// 이미 있는 부분이 링크나 파일이면 대상 폴더 밖에 기록하게 될 수 있으므로 거부
+pub fn create_parent_dirs(root: &Path, path: &Path) -> Result<Vec<PathBuf>, String> {
+    let mut created = Vec::new();
+    let Some(relative) = path.parent().and_then(|parent| parent.strip_prefix(root).ok()) else { return Ok(created) };
+    let mut dir = root.to_path_buf();
+    for component in relative.components() {
+        dir.push(component);
+        match fs::symlink_metadata(&dir) {
+            Ok(metadata) if metadata.is_dir() => {}
+            Ok(_) => return Err(format!("{} is not a folder", dir.display())),
+            Err(_) => {
+                fs::create_dir(&dir).map_err(|e| format!("Failed to create {}: {}", dir.display(), e))?;
+                created.push(dir.clone());
+            }
+        }
+    }
+    Ok(created)
+}
+
+/******************* 전부 성공했을 때만 결과를 반영하는 작업용 임시 폴더 ******************/
+// 출력 파일을 대상 폴더 안의 .securevault-staging-<작업 ID> 에 먼저 기록하고,
+// 모든 파일이 성공하면 이름을 바꿔서 제자리로 옮기고(commit), 하나라도 실패하면 모두 지움(rollback)
+// 같은 볼륨 안에서의 이름 변경이므로 파일 내용을 다시 복사하지 않음
+pub struct Staging {
+    dir: PathBuf,
+    // (임시 폴더 안의 경로, 최종 경로)
+    entries: Vec<(PathBuf, PathBuf)>,
+    // 복호화 결과처럼 평문이 담긴 파일은 되돌릴 때 덮어쓴 뒤 삭제
+    wipe_on_rollback: bool,
+    // commit 하면서 새로 만든 하위 폴더 (되돌릴 때 비어 있으면 삭제)
+    created_dirs: Vec<PathBuf>,
+}
+
+impl Staging {
+    pub fn create(destination_dir: &Path, job_id: &str, wipe_on_rollback: bool) -> Result<Self, String> {
+        let dir = destination_dir.join(format!(".securevault-staging-{}", job_id));
+        fs::create_dir(&dir).map_err(|e| format!("Failed to create staging folder: {}", e))?;
+        Ok(Staging { dir, entries: Vec::new(), wipe_on_rollback, created_dirs: Vec::new() })
+    }
+
+    // 최종 경로 대신 기록할 임시 경로 (같은 이름의 출력이 여러 개여도 겹치지 않도록 순번을 붙임)
+    pub fn stage(&mut self, final_path: &Path) -> PathBuf {
+        let file_name = final_path.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+        let staged_path = self.dir.join(format!("{:06}-{}", self.entries.len(), file_name));
+        self.entries.push((staged_path.clone(), final_path.to_path_buf()));
+        staged_path
+    }
+
+    /******************* 임시 폴더의 파일을 모두 최종 경로로 옮김 ******************/
+    // 기존 파일을 덮어쓰게 되거나 옮기는 중에 실패하면, 이미 옮긴 파일도 되돌린 뒤 모두 지우고 이유를 반환
+    pub fn commit(mut self) -> Result<(), String> {
+        if let Err(reason) = self.check_conflicts() {
+            self.rollback();
+            return Err(reason);
+        }
+
+        let root = self.dir.parent().map(Path::to_path_buf).unwrap_or_default();
+        for index in 0..self.entries.len() {
+            let (staged_path, final_path) = &self.entries[index];
+            let moved = create_parent_dirs(&root, final_path)
+                .map(|created| self.created_dirs.extend(created))
+                .and_then(|_| fs::rename(staged_path, final_path).map_err(|e| e.to_string()));
+            if let Err(e) = moved {
+                let reason = format!("Failed to move {} into place: {}", final_path.display(), e);
+                // 이미 옮긴 파일은 임시 폴더로 되돌려서 함께 지움
+                for (staged_path, final_path) in &self.entries[..index] {
+                    let _ = fs::rename(final_path, staged_path);
+                }
+                self.rollback();
+                return Err(reason);
+            }
+        }
+        self.entries.clear();
+        let _ = fs::remove_dir_all(&self.dir);
+        Ok(())
+    }
+
+    // 옮기기 전에 기존 파일을 덮어쓰거나 두 출력이 같은 경로로 가는 경우를 확인
+    fn check_conflicts(&self) -> Result<(), String> {
+        let mut seen = HashSet::new();
+        for (_, final_path) in &self.entries {
+            if !seen.insert(final_path) {
+                return Err(format!("More than one file would be written to {}", final_path.display()));
+            }
+            ensure_absent(final_path)?;
+        }
+        Ok(())
+    }
+
+    /******************* 임시 폴더에 기록한 파일을 모두 삭제 ******************/
+    pub fn rollback(mut self) {
+        let never_cancel = AtomicBool::new(false);
+        for (staged_path, _) in self.entries.drain(..) {
+            if self.wipe_on_rollback && staged_path.is_file() {
+                let _ = wipe::wipe_file(&staged_path, &WipeOptions::default(), &never_cancel, &mut |_| {});
+            }
+        }
+        for dir in self.created_dirs.drain(..).rev() {
+            let _ = fs::remove_dir(&dir);
+        }
+        let _ = fs::remove_dir_all(&self.dir);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn create_parent_dirs_refuses_links_out_of_destination() {
+        let root = std::env::temp_dir().join(format!("staging-test-{}", std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(&root).unwrap();
+        let created = create_parent_dirs(&root, &root.join("a").join("b").join("file.txt")).unwrap();
+        assert_eq!(created, vec![root.join("a"), root.join("a").join("b")]);
+        #[cfg(unix)]
+        {
+            std::os::unix::fs::symlink(std::env::temp_dir(), root.join("link")).unwrap();
+            assert!(create_parent_dirs(&root, &root.join("link").join("file.txt")).is_err());
+        }
+        let _ = fs::remove_dir_all(&root);
+    }
+}
@@ -0,0 +1,276 @@
+use serde::Serialize;
+use std::collections::HashMap;
+use std::fs;
+use std::path::Path;
+
+// 덮어쓰기로 원본 데이터가 실제로 지워졌다고 볼 수 있는 정도
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "SCREAMING_SNAKE_CASE")]
+pub enum WipeConfidence {
+    // 제자리 덮어쓰기가 보장되는 파일 시스템 + 회전식 디스크(HDD) 또는 메모리
+    High,
+    // SSD/플래시: 웨어 레벨링 때문에 이전 블록이 남아 있을 수 있음
+    Low,
+    // Copy-on-Write/로그 구조 파일 시스템: 덮어쓰기가 새 블록에 기록되어 원본은 그대로 남음
+    None,
+    // 네트워크 파일 시스템이거나 판별할 수 없는 경우
+    Unknown,
+}
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum FsKind {
+    InPlace,
+    CopyOnWrite,
+    Memory,
+    Remote,
+    Unknown,
+}
+
+// 대상 파일이 놓인 파일 시스템과 장치 정보
+#[derive(Clone, Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct StorageInfo {
+    pub filesystem: Option<String>,
+    pub rotational: Option<bool>,
+    pub confidence: WipeConfidence,
+    pub warning: Option<String>,
+}
+
+impl StorageInfo {
+    fn new(filesystem: Option<&str>, kind: FsKind, rotational: Option<bool>) -> Self {
+        let (confidence, warning) = match (kind, rotational) {
+            (FsKind::CopyOnWrite, _) => (
+                WipeConfidence::None,
+                Some("Copy-on-write filesystem: overwriting writes new blocks and the original data remains on disk".to_string()),
+            ),
+            (FsKind::Remote, _) => (
+                WipeConfidence::Unknown,
+                Some("Network filesystem: the server decides whether data is overwritten in place".to_string()),
+            ),
+            (FsKind::Memory, _) => (WipeConfidence::High, None),
+            (_, Some(false)) => (
+                WipeConfidence::Low,
+                Some("Solid-state storage: wear levelling may keep copies of the original blocks".to_string()),
+            ),
+            (FsKind::InPlace, Some(true)) => (WipeConfidence::High, None),
+            _ => (
+                WipeConfidence::Unknown,
+                Some("Could not determine the storage type, overwriting may not reach the original blocks".to_string()),
+            ),
+        };
+        StorageInfo { filesystem: filesystem.map(str::to_string), rotational, confidence, warning }
+    }
+}
+
+/******************* 경로가 놓인 파일 시스템과 장치 종류 확인 ******************/
+pub fn detect(path: &Path) -> StorageInfo {
+    platform::detect(path)
+}
+
+// 같은 장치에 있는 파일은 한 번만 확인하도록 장치 번호별로 결과를 저장
+#[derive(Default)]
+pub struct StorageProbe {
+    cache: HashMap<u64, StorageInfo>,
+}
+
+impl StorageProbe {
+    pub fn detect(&mut self, path: &Path, device: Option<u64>) -> StorageInfo {
+        match device {
+            Some(device) => self.cache.entry(device).or_insert_with(|| detect(path)).clone(),
+            None => detect(path),
+        }
+    }
+}
+
+// 메타데이터에서 장치 번호를 가져옴 (확인할 수 없는 플랫폼에서는 None)
+pub fn device_id(metadata: &fs::Metadata) -> Option<u64> {
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::MetadataExt;
+        Some(metadata.dev())
+    }
+    #[cfg(not(unix))]
+    {
+        let _ = metadata;
+        None
+    }
+}
+
+/******************* 파일 내용이 차지하던 블록을 해제 (hole punching) ******************/
+// 파일 시스템이 discard 옵션으로 마운트되어 있으면 해제된 블록에 대해 TRIM 도 함께 전달됨
+pub fn punch_hole(file: &fs::File, len: u64) -> Result<(), String> {
+    if len == 0 {
+        return Ok(());
+    }
+    platform::punch_hole(file, len)
+}
+
+/******************* 일반 사용자가 쓸 수 있는 남은 공간(바이트) ******************/
+#[cfg(unix)]
+pub fn available_space(path: &Path) -> Result<u64, String> {
+    use std::ffi::CString;
+    use std::os::unix::ffi::OsStrExt;
+
+    let c_path = CString::new(path.as_os_str().as_bytes()).map_err(|e| e.to_string())?;
+    let mut stat: libc::statvfs = unsafe { std::mem::zeroed() };
+    if unsafe { libc::statvfs(c_path.as_ptr(), &mut stat) } != 0 {
+        return Err(std::io::Error::last_os_error().to_string());
+    }
+    #[allow(clippy::unnecessary_cast)]
+    Ok(stat.f_bavail as u64 * stat.f_frsize as u64)
+}
+
+// 디스크 할당량이 있으면 그만큼만 반환하는 GetDiskFreeSpaceExW 의 첫 번째 값을 사용 (kernel32 는 기본으로 링크됨)
+#[cfg(windows)]
+pub fn available_space(path: &Path) -> Result<u64, String> {
+    use std::os::windows::ffi::OsStrExt;
+
+    #[link(name = "kernel32")]
+    extern "system" {
+        fn GetDiskFreeSpaceExW(directory: *const u16, free_to_caller: *mut u64, total: *mut u64, total_free: *mut u64) -> i32;
+    }
+
+    let wide_path: Vec<u16> = path.as_os_str().encode_wide().chain(std::iter::once(0)).collect();
+    let mut free_to_caller = 0u64;
+    let ok = unsafe { GetDiskFreeSpaceExW(wide_path.as_ptr(), &mut free_to_caller, std::ptr::null_mut(), std::ptr::null_mut()) };
+    if ok == 0 {
+        return Err(std::io::Error::last_os_error().to_string());
+    }
+    Ok(free_to_caller)
+}
+
+#[cfg(not(any(unix, windows)))]
+pub fn available_space(_path: &Path) -> Result<u64, String> {
+    Err("Checking free space is not supported on this platform".to_string())
+}
+
+#[cfg(target_os = "linux")]
+mod platform {
+    use super::{FsKind, StorageInfo};
+    use std::ffi::CString;
+    use std::fs;
+    use std::os::unix::ffi::OsStrExt;
+    use std::os::unix::fs::MetadataExt;
+    use std::os::unix::io::AsRawFd;
+    use std::path::Path;
+
+    // statfs(2) 의 f_type 값 (linux/magic.h)
+    fn classify_magic(magic: u32) -> (&'static str, FsKind) {
+        match magic {
+            0xEF53 => ("ext2/3/4", FsKind::InPlace),
+            0x5846_5342 => ("xfs", FsKind::InPlace),
+            0x4D44 => ("vfat", FsKind::InPlace),
+            0x2011_BAB0 => ("exfat", FsKind::InPlace),
+            0x5346_544E | 0x7366_746E => ("ntfs", FsKind::InPlace),
+            0x6573_5546 => ("fuse", FsKind::Unknown),
+            0x9123_683E => ("btrfs", FsKind::CopyOnWrite),
+            0x2FC1_2FC1 => ("zfs", FsKind::CopyOnWrite),
+            0xCA45_1A4E => ("bcachefs", FsKind::CopyOnWrite),
+            0xF2F5_2010 => ("f2fs", FsKind::CopyOnWrite),
+            0x3434 => ("nilfs2", FsKind::CopyOnWrite),
+            0x794C_7630 => ("overlayfs", FsKind::CopyOnWrite),
+            0x0102_1994 => ("tmpfs", FsKind::Memory),
+            0x8584_58F6 => ("ramfs", FsKind::Memory),
+            0x6969 => ("nfs", FsKind::Remote),
+            0x517B | 0xFF53_4D42 | 0xFE53_4D42 => ("smb", FsKind::Remote),
+            _ => ("unknown", FsKind::Unknown),
+        }
+    }
+
+    fn statfs_magic(path: &Path) -> Option<u32> {
+        let c_path = CString::new(path.as_os_str().as_bytes()).ok()?;
+        let mut stat: libc::statfs = unsafe { std::mem::zeroed() };
+        if unsafe { libc::statfs(c_path.as_ptr(), &mut stat) } != 0 {
+            return None;
+        }
+        // f_type 의 타입은 아키텍처마다 다르므로 하위 32비트만 비교
+        Some(stat.f_type as u32)
+    }
+
+    // /sys/dev/block/<major>:<minor>/queue/rotational 로 HDD 여부 확인 (파티션이면 상위 디스크의 값을 사용)
+    fn rotational(device: u64) -> Option<bool> {
+        let major = libc::major(device as libc::dev_t);
+        let minor = libc::minor(device as libc::dev_t);
+        // major 0 은 btrfs 서브볼륨, tmpfs 등의 가상 장치
+        if major == 0 {
+            return None;
+        }
+        let base = format!("/sys/dev/block/{}:{}", major, minor);
+        [format!("{}/queue/rotational", base), format!("{}/../queue/rotational", base)]
+            .iter()
+            .find_map(|p| fs::read_to_string(p).ok())
+            .map(|value| value.trim() == "1")
+    }
+
+    pub fn detect(path: &Path) -> StorageInfo {
+        let (name, kind) = match statfs_magic(path) {
+            Some(magic) => classify_magic(magic),
+            None => ("unknown", FsKind::Unknown),
+        };
+        let rotational = fs::symlink_metadata(path).ok().and_then(|m| rotational(m.dev()));
+        StorageInfo::new(Some(name), kind, rotational)
+    }
+
+    pub fn punch_hole(file: &fs::File, len: u64) -> Result<(), String> {
+        let mode = libc::FALLOC_FL_PUNCH_HOLE | libc::FALLOC_FL_KEEP_SIZE;
+        if unsafe { libc::fallocate(file.as_raw_fd(), mode, 0, len as libc::off_t) } != 0 {
+            return Err(std::io::Error::last_os_error().to_string());
+        }
+        Ok(())
+    }
+}
+
+#[cfg(target_os = "macos")]
+mod platform {
+    use super::{FsKind, StorageInfo};
+    use std::ffi::{CStr, CString};
+    use std::fs;
+    use std::os::unix::ffi::OsStrExt;
+    use std::os::unix::io::AsRawFd;
+    use std::path::Path;
+
+    fn fs_type_name(path: &Path) -> Option<String> {
+        let c_path = CString::new(path.as_os_str().as_bytes()).ok()?;
+        let mut stat: libc::statfs = unsafe { std::mem::zeroed() };
+        if unsafe { libc::statfs(c_path.as_ptr(), &mut stat) } != 0 {
+            return None;
+        }
+        let name = unsafe { CStr::from_ptr(stat.f_fstypename.as_ptr()) };
+        Some(name.to_string_lossy().to_string())
+    }
+
+    // macOS 에서는 장치의 회전 여부를 간단히 알 수 없으므로 파일 시스템 종류만 판별
+    pub fn detect(path: &Path) -> StorageInfo {
+        let name = fs_type_name(path);
+        let kind = match name.as_deref() {
+            Some("apfs") => FsKind::CopyOnWrite,
+            Some("hfs") | Some("msdos") | Some("exfat") => FsKind::InPlace,
+            Some("nfs") | Some("smbfs") | Some("afpfs") | Some("webdav") => FsKind::Remote,
+            _ => FsKind::Unknown,
+        };
+        StorageInfo::new(name.as_deref(), kind, None)
+    }
+
+    pub fn punch_hole(file: &fs::File, len: u64) -> Result<(), String> {
+        let args = libc::fpunchhole_t { fp_flags: 0, reserved: 0, fp_offset: 0, fp_length: len as libc::off_t };
+        if unsafe { libc::fcntl(file.as_raw_fd(), libc::F_PUNCHHOLE, &args) } != 0 {
+            return Err(std::io::Error::last_os_error().to_string());
+        }
+        Ok(())
+    }
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "macos")))]
+mod platform {
+    use super::{FsKind, StorageInfo};
+    use std::fs;
+    use std::path::Path;
+
+    pub fn detect(_path: &Path) -> StorageInfo {
+        StorageInfo::new(None, FsKind::Unknown, None)
+    }
+
+    pub fn punch_hole(_file: &fs::File, _len: u64) -> Result<(), String> {
+        Err("Hole punching is not supported on this platform".to_string())
+    }
+}
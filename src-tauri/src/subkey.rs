@@ -0,0 +1,44 @@
+use aes_gcm::{Aes256Gcm, Key};
+use hkdf::Hkdf;
+use sha2::Sha256;
+
+/******************* 용도별 하위 키 ******************/
+// 마스터 키나 파일 키를 여러 곳에 그대로 쓰지 않고, 용도마다 다른 info 로 HKDF-SHA256 을 돌려 별도의 키를 만듦
+// 한 기능에 결함(논스 재사용 등)이 있어도 다른 기능의 키는 영향을 받지 않음. 새 기능은 여기에 용도를 추가해서 키를 받음
+// info 문자열은 이미 저장된 파일을 열 때 필요하므로 바꾸면 안 됨 (형식을 바꿀 때는 버전을 올린 새 용도로 추가)
+#[derive(Clone, Copy)]
+pub enum Purpose {
+    // 파일 키에서 유도
+    Metadata,
+    Content,
+    // 마스터 키에서 유도
+    DataKeyWrap,
+    AuditLog,
+    KeyBook,
+    KeyRing,
+    Catalog,  // 폴더 미러 목록
+    Filename, // 폴더 미러 출력 이름
+}
+
+impl Purpose {
+    fn info(self) -> &'static [u8] {
+        match self {
+            Purpose::Metadata => b"securevault metadata key v1",
+            Purpose::Content => b"securevault content key v1",
+            Purpose::DataKeyWrap => b"securevault data key wrap v1",
+            Purpose::AuditLog => b"securevault audit log v1",
+            Purpose::KeyBook => b"securevault keybook v1",
+            Purpose::KeyRing => b"securevault keyring v1",
+            Purpose::Catalog => b"securevault catalog v1",
+            Purpose::Filename => b"securevault filename v1",
+        }
+    }
+}
+
+pub fn derive(key: &Key<Aes256Gcm>, purpose: Purpose) -> Key<Aes256Gcm> {
+    let mut okm = [0u8; 32];
+    Hkdf::<Sha256>::new(None, key.as_slice())
+        .expand(purpose.info(), &mut okm)
+        .expect("32 bytes is a valid HKDF-SHA256 output length");
+    Key::<Aes256Gcm>::from(okm)
+}
@@ -0,0 +1,251 @@
+use hkdf::Hkdf;
+use hmac::{Hmac, Mac};
+use serde::{Deserialize, Serialize};
+use sha2::Sha256;
+use std::fs;
+use std::path::{Path, PathBuf};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::progress;
+use crate::report;
+use crate::vault_file;
+
+const THROTTLE_KEY_INFO: &[u8] = b"securevault unlock throttle v1";
+const MAC_LEN: usize = 32;
+
+/******************* 잠금 해제 실패 시 대기 정책 ******************/
+// free_attempts 번까지는 바로 다시 시도할 수 있고, 그 뒤로는 실패할 때마다 대기 시간을 두 배로 늘림
+// wipe_after 를 정하면 연속으로 그만큼 실패했을 때 vault 파일을 덮어쓴 뒤 삭제함 (암호화된 파일은 복구 불가)
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct UnlockPolicy {
+    pub free_attempts: u32,
+    pub base_delay_seconds: u64,
+    pub max_delay_seconds: u64,
+    pub wipe_after: Option<u32>,
+}
+
+impl Default for UnlockPolicy {
+    fn default() -> Self {
+        UnlockPolicy { free_attempts: 3, base_delay_seconds: 2, max_delay_seconds: 300, wipe_after: None }
+    }
+}
+
+impl UnlockPolicy {
+    // 연속 실패 횟수에 따른 다음 시도까지의 대기 시간(초)
+    pub fn delay_seconds(&self, failures: u32) -> u64 {
+        if failures < self.free_attempts.max(1) {
+            return 0;
+        }
+        let doublings = (failures - self.free_attempts.max(1)).min(32);
+        self.base_delay_seconds.saturating_mul(1u64 << doublings).min(self.max_delay_seconds)
+    }
+
+    pub fn validate(&self) -> Result<(), String> {
+        if self.max_delay_seconds < self.base_delay_seconds {
+            return Err("Maximum delay must not be shorter than the base delay".into());
+        }
+        if self.wipe_after == Some(0) {
+            return Err("Wipe threshold must be at least 1".into());
+        }
+        Ok(())
+    }
+}
+
+// 마지막으로 잠금 해제에 성공한 뒤의 연속 실패 기록
+#[derive(Clone, Default, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct ThrottleState {
+    pub failures: u32,        // 실제로 틀린 횟수만 셈 (삭제 기준에 쓰임)
+    pub last_failure_at: u64, // 유닉스 시간(초)
+    pub tampered: bool,       // 기록이 변조되거나 손상되어 최대 대기 시간을 적용 중 (실패 횟수에는 더하지 않음)
+}
+
+// 잠금 화면에 보여줄 현재 상태
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UnlockStatus {
+    pub failed_attempts: u32,
+    pub retry_after_seconds: u64,
+    pub attempts_before_wipe: Option<u32>,
+    pub tampered: bool,
+}
+
+// 잠금 해제에 성공했을 때 알려줄 지난 실패 기록
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct UnlockSummary {
+    pub failed_attempts: u32,
+    pub last_failed_at: Option<String>,
+    pub warnings: Vec<String>, // 잠금 해제는 되었지만 저장하지 못한 기록 등 알려야 할 문제
+}
+
+/******************* 잠금 해제 실패 기록 (vault 파일 옆의 <이름>.throttle) ******************/
+// 잠겨 있는 동안에도 읽어야 하므로 마스터 키 대신 vault 파일 내용에서 유도한 키로 HMAC 을 붙여 저장
+// 이 키는 vault 파일을 읽을 수 있는 사람이면 누구나 만들 수 있으므로 인증이 아니라 무결성 확인일 뿐임:
+// 다른 vault 의 기록을 복사하거나, 쓰다가 손상되거나, 키를 모르고 내용을 고친 경우만 찾아냄
+// 검증에 실패하면 경고와 함께 최대 대기 시간을 적용하지만, 실수로 지워지는 일이 없도록 삭제 기준의 실패 횟수에는 더하지 않음
+// vault 를 만들거나 가져올 때 항상 기록을 남기므로, 기록 파일이 없는 것도 변조로 봄
+// (키를 아는 사람이 초기 상태를 새로 만드는 것은 막지 못하며, vault 파일을 복사해 앱 밖에서 시도하는 공격은 Argon2 비용으로만 늦출 수 있음)
+pub struct Throttle {
+    path: PathBuf,
+    key: [u8; 32],
+}
+
+impl Throttle {
+    pub fn new(vault_path: &Path, vault_data: &[u8]) -> Self {
+        let mut key = [0u8; 32];
+        Hkdf::<Sha256>::new(None, vault_data)
+            .expand(THROTTLE_KEY_INFO, &mut key)
+            .expect("32 bytes is a valid HKDF-SHA256 output length");
+        Throttle { path: throttle_path(vault_path), key }
+    }
+
+    pub fn load(&self) -> ThrottleState {
+        let data = fs::read(&self.path).unwrap_or_default();
+        let state = (data.len() > MAC_LEN)
+            .then(|| data.split_at(MAC_LEN))
+            .filter(|(tag, body)| self.mac(body).verify_slice(tag).is_ok())
+            .and_then(|(_, body)| serde_json::from_slice::<ThrottleState>(body).ok());
+        match state {
+            Some(state) => state,
+            None => {
+                // 저장하지 못하면 읽을 때마다 지금부터 최대 대기 시간을 적용하므로 더 엄격한 쪽으로 동작함
+                let state = ThrottleState { failures: 0, last_failure_at: now_seconds(), tampered: true };
+                let _ = self.save(&state);
+                state
+            }
+        }
+    }
+
+    pub fn save(&self, state: &ThrottleState) -> Result<(), String> {
+        let body = serde_json::to_vec(state).map_err(|e| e.to_string())?;
+        let mut data = self.mac(&body).finalize().into_bytes().to_vec();
+        data.extend_from_slice(&body);
+        progress::write_with_progress(&self.path, &data, &mut |_| {})
+            .map_err(|e| format!("Failed to save unlock throttle state: {}", e))
+    }
+
+    pub fn status(&self, policy: &UnlockPolicy) -> UnlockStatus {
+        let state = self.load();
+        UnlockStatus {
+            failed_attempts: state.failures,
+            retry_after_seconds: retry_after(&state, policy),
+            attempts_before_wipe: policy.wipe_after.map(|limit| limit.saturating_sub(state.failures)),
+            tampered: state.tampered,
+        }
+    }
+
+    // 대기 시간이 남아 있으면 비밀번호를 확인하지 않고 거부
+    pub fn check(&self, policy: &UnlockPolicy) -> Result<(), String> {
+        let wait = retry_after(&self.load(), policy);
+        if wait > 0 {
+            return Err(format!("Too many failed attempts. Try again in {} seconds.", wait));
+        }
+        Ok(())
+    }
+
+    // 실패 횟수를 늘리고, 삭제 기준에 도달했는지 반환 (기록하지 못하면 대기 시간이 적용되지 않으므로 오류)
+    pub fn record_failure(&self, policy: &UnlockPolicy) -> Result<bool, String> {
+        let mut state = self.load();
+        state.failures = state.failures.saturating_add(1);
+        state.last_failure_at = now_seconds();
+        self.save(&state)?;
+        Ok(policy.wipe_after.is_some_and(|limit| state.failures >= limit))
+    }
+
+    // 성공 시 기록을 초기화하고 그동안의 실패 횟수를 반환
+    pub fn record_success(&self) -> UnlockSummary {
+        let state = self.load();
+        let mut summary = UnlockSummary {
+            failed_attempts: if state.tampered { 0 } else { state.failures },
+            last_failed_at: (state.failures > 0 && !state.tampered).then(|| report::format_utc(state.last_failure_at, 0)),
+            warnings: Vec::new(),
+        };
+        if let Err(e) = self.reset() {
+            summary.warnings.push(e);
+        }
+        summary
+    }
+
+    // vault 파일이 바뀌면(비밀번호 변경 등) 새 키로 초기 상태를 저장
+    pub fn reset(&self) -> Result<(), String> {
+        self.save(&ThrottleState::default())
+    }
+
+    fn mac(&self, body: &[u8]) -> Hmac<Sha256> {
+        let mut mac = <Hmac<Sha256> as Mac>::new_from_slice(&self.key).expect("HMAC accepts any key length");
+        mac.update(body);
+        mac
+    }
+}
+
+// vault.key → vault.key.throttle
+pub fn throttle_path(vault_path: &Path) -> PathBuf {
+    vault_file::side_path(vault_path, "throttle")
+}
+
+fn retry_after(state: &ThrottleState, policy: &UnlockPolicy) -> u64 {
+    let delay = if state.tampered { policy.max_delay_seconds } else { policy.delay_seconds(state.failures) };
+    // 시계가 뒤로 돌아간 경우에도 대기 시간 전체를 적용
+    let elapsed = now_seconds().saturating_sub(state.last_failure_at);
+    delay.saturating_sub(elapsed)
+}
+
+fn now_seconds() -> u64 {
+    SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.as_secs()).unwrap_or(0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn tampered_state_never_reaches_wipe_threshold() {
+        let dir = std::env::temp_dir().join(format!("throttle-test-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let vault_path = dir.join("vault.key");
+        let throttle = Throttle::new(&vault_path, b"vault file contents");
+        fs::write(throttle_path(&vault_path), b"not a valid record").unwrap();
+        let policy = UnlockPolicy { wipe_after: Some(1), ..Default::default() };
+
+        let status = throttle.status(&policy);
+        assert!(status.tampered);
+        assert_eq!(status.attempts_before_wipe, Some(1));
+        assert_eq!(status.retry_after_seconds, policy.max_delay_seconds);
+        // 변조 후 처음 틀린 비밀번호는 실제 실패 1회로만 셈
+        assert!(!throttle.record_failure(&UnlockPolicy { wipe_after: Some(2), ..Default::default() }).unwrap());
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn missing_record_is_treated_as_tampered() {
+        let dir = std::env::temp_dir().join(format!("throttle-missing-test-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let vault_path = dir.join("vault.key");
+        let throttle = Throttle::new(&vault_path, b"vault file contents");
+        let policy = UnlockPolicy::default();
+
+        throttle.reset().unwrap();
+        assert!(!throttle.status(&policy).tampered);
+        fs::remove_file(throttle_path(&vault_path)).unwrap();
+        let status = throttle.status(&policy);
+        assert!(status.tampered);
+        assert_eq!(status.failed_attempts, 0);
+        assert_eq!(status.retry_after_seconds, policy.max_delay_seconds);
+        fs::remove_dir_all(&dir).unwrap();
+    }
+
+    #[test]
+    fn delay_doubles_after_free_attempts_up_to_the_maximum() {
+        let policy = UnlockPolicy { free_attempts: 3, base_delay_seconds: 2, max_delay_seconds: 300, wipe_after: None };
+        let delays: Vec<u64> = (0..12).map(|failures| policy.delay_seconds(failures)).collect();
+        assert_eq!(delays, [0, 0, 0, 2, 4, 8, 16, 32, 64, 128, 256, 300]);
+        assert_eq!(policy.delay_seconds(u32::MAX), 300);
+
+        // 무료 시도가 0 이어도 첫 실패 전에는 대기하지 않음
+        let strict = UnlockPolicy { free_attempts: 0, ..policy };
+        assert_eq!(strict.delay_seconds(0), 0);
+        assert_eq!(strict.delay_seconds(1), 2);
+    }
+}
@@ -0,0 +1,146 @@
+use aes_gcm::aead::Aead;
+use aes_gcm::{Aes256Gcm, Key, KeyInit, Nonce};
+use argon2::{
+    password_hash::{PasswordHasher, SaltString},
+    Argon2, Params, Version,
+};
+use rand::RngCore;
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::progress;
+
+const SALT_LEN: usize = 16;
+const NONCE_LEN: usize = 12;
+const HEADER_MIN_LEN: usize = SALT_LEN + NONCE_LEN;
+
+// vault 파일을 다시 쓸 때마다 남겨 두는 이전 버전 수 (<이름>.bak.1 이 가장 최근)
+pub const BACKUP_COUNT: usize = 5;
+
+/******************* 비밀번호로 KEK(마스터 키를 암호화할 키) 생성 ******************/
+fn derive_kek(password: &str, salt_bytes: &[u8]) -> Result<Key<Aes256Gcm>, String> {
+    let salt = SaltString::encode_b64(salt_bytes).map_err(|e| e.to_string())?;
+    let argon2 = Argon2::new(
+        argon2::Algorithm::Argon2id,
+        Version::V0x13,
+        Params::new(15000, 2, 1, None).unwrap()
+    );
+    let kek_hash = argon2.hash_password(password.as_bytes(), &salt).map_err(|e| e.to_string())?;
+    let binding = kek_hash.hash.unwrap();
+    Ok(*Key::<Aes256Gcm>::from_slice(&binding.as_bytes()[..32]))
+}
+
+/******************* 마스터 키를 비밀번호로 암호화하여 vault 파일 내용을 만듦 ******************/
+// [솔트(16)] + [논스(12)] + [암호화된 마스터 키] 형태
+pub fn wrap_vault_key(vault_key: &[u8], password: &str) -> Result<Vec<u8>, String> {
+    let mut salt_bytes = [0u8; SALT_LEN];
+    rand::rng().fill_bytes(&mut salt_bytes);
+    let kek = derive_kek(password, &salt_bytes)?;
+
+    let cipher = Aes256Gcm::new(&kek);
+    let mut nonce_bytes = [0u8; NONCE_LEN];
+    rand::rng().fill_bytes(&mut nonce_bytes);
+    let encrypted_vault_key = cipher.encrypt(Nonce::from_slice(&nonce_bytes), vault_key).map_err(|e| e.to_string())?;
+
+    let mut final_data = Vec::new();
+    final_data.extend_from_slice(&salt_bytes);
+    final_data.extend_from_slice(&nonce_bytes);
+    final_data.extend_from_slice(&encrypted_vault_key);
+    Ok(final_data)
+}
+
+/******************* vault 파일 내용에서 마스터 키를 복호화 ******************/
+// 파일 형식이 잘못되면 Err, 비밀번호가 틀리면 Ok(None)
+pub fn unwrap_vault_key(vault_data: &[u8], password: &str) -> Result<Option<Vec<u8>>, String> {
+    if vault_data.len() < HEADER_MIN_LEN { return Err("Invalid vault file".into()); }
+    let salt_bytes = &vault_data[..SALT_LEN];
+    let nonce_bytes = &vault_data[SALT_LEN..HEADER_MIN_LEN];
+    let encrypted_vault_key = &vault_data[HEADER_MIN_LEN..];
+
+    let kek = derive_kek(password, salt_bytes)?;
+    let cipher = Aes256Gcm::new(&kek);
+    Ok(cipher.decrypt(Nonce::from_slice(nonce_bytes), encrypted_vault_key).ok())
+}
+
+pub fn check_format(vault_data: &[u8]) -> Result<(), String> {
+    if vault_data.len() < HEADER_MIN_LEN { return Err("Invalid vault file".into()); }
+    Ok(())
+}
+
+/******************* 기존 vault 파일을 백업으로 옮긴 뒤 새 내용으로 교체 ******************/
+// <이름>.bak.1 ~ <이름>.bak.N 을 한 칸씩 밀고 가장 오래된 것은 삭제
+// 백업은 이전 비밀번호로 암호화되어 있으므로, 이전 비밀번호를 아는 사람은 백업으로 마스터 키를 꺼낼 수 있음
+pub fn write_with_backup(vault_path: &Path, data: &[u8]) -> Result<(), String> {
+    if vault_path.is_file() {
+        let backups = backup_paths(vault_path);
+        for index in (1..backups.len()).rev() {
+            if backups[index - 1].exists() {
+                fs::rename(&backups[index - 1], &backups[index]).map_err(|e| e.to_string())?;
+            }
+        }
+        fs::copy(vault_path, &backups[0]).map_err(|e| e.to_string())?;
+    }
+    progress::write_with_progress(vault_path, data, &mut |_| {})
+}
+
+/******************* 이전 백업을 모두 지우고 새 내용과 그 백업만 남김 ******************/
+// 비밀번호를 바꿀 때 사용. 이전 비밀번호로 암호화된 백업은 wipe 로 지우고(덮어쓴 뒤 삭제),
+// 새 내용을 쓴 뒤 같은 내용을 <이름>.bak.1 로 남겨 새 비밀번호로 된 백업이 하나는 있게 함
+pub fn write_replacing_backups(
+    vault_path: &Path,
+    data: &[u8],
+    wipe: &mut dyn FnMut(&Path) -> Result<(), String>,
+) -> Result<(), String> {
+    let backups = backup_paths(vault_path);
+    for backup in backups.iter().filter(|p| p.is_file()) {
+        wipe(backup)?;
+    }
+    progress::write_with_progress(vault_path, data, &mut |_| {})?;
+    progress::write_with_progress(&backups[0], data, &mut |_| {})
+}
+
+/******************* vault 파일 옆에 두는 파일 이름 ******************/
+// vault.key → vault.key.audit, vault.key.throttle ...
+// 확장자를 바꾸지 않고 전체 파일 이름 뒤에 붙이므로, 같은 폴더의 work.key 와 work.vault 가 서로의 파일을 쓰지 않음
+pub fn side_path(vault_path: &Path, suffix: &str) -> PathBuf {
+    let file_name = vault_path.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+    vault_path.with_file_name(format!("{}.{}", file_name, suffix))
+}
+
+// vault.key → vault.key.bak.1 ... vault.key.bak.N
+pub fn backup_paths(vault_path: &Path) -> Vec<PathBuf> {
+    let file_name = vault_path.file_name().map(|n| n.to_string_lossy().to_string()).unwrap_or_default();
+    (1..=BACKUP_COUNT).map(|index| vault_path.with_file_name(format!("{}.bak.{}", file_name, index))).collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn side_files_do_not_collide_for_vaults_sharing_a_stem() {
+        let dir = std::env::temp_dir();
+        assert_ne!(side_path(&dir.join("work.key"), "audit"), side_path(&dir.join("work.vault"), "audit"));
+        assert_eq!(side_path(&dir.join("work.key"), "throttle"), dir.join("work.key.throttle"));
+    }
+
+    #[test]
+    fn password_change_keeps_a_backup_under_the_new_password() {
+        let dir = std::env::temp_dir().join(format!("vault-file-backup-test-{}", std::process::id()));
+        fs::create_dir_all(&dir).unwrap();
+        let vault_path = dir.join("vault.key");
+        let vault_key = [7u8; 32];
+        write_with_backup(&vault_path, &wrap_vault_key(&vault_key, "old").unwrap()).unwrap();
+        write_with_backup(&vault_path, &wrap_vault_key(&vault_key, "old").unwrap()).unwrap();
+        assert!(backup_paths(&vault_path)[0].is_file());
+
+        let new_data = wrap_vault_key(&vault_key, "new").unwrap();
+        write_replacing_backups(&vault_path, &new_data, &mut |path| fs::remove_file(path).map_err(|e| e.to_string())).unwrap();
+
+        let backups = backup_paths(&vault_path);
+        assert_eq!(fs::read(&vault_path).unwrap(), new_data);
+        assert_eq!(unwrap_vault_key(&fs::read(&backups[0]).unwrap(), "new").unwrap(), Some(vault_key.to_vec()));
+        assert!(backups[1..].iter().all(|p| !p.exists()));
+        fs::remove_dir_all(&dir).unwrap();
+    }
+}
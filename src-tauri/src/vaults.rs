@@ -0,0 +1,134 @@
+use serde::{Deserialize, Serialize};
+use std::fs;
+use std::path::{Path, PathBuf};
+use uuid::Uuid;
+
+use crate::progress;
+
+// 목록 파일이 없을 때 기존 위치의 vault 에 붙이는 ID
+// 목록을 저장하기 전까지는 읽을 때마다 새로 만들어지므로, list_vaults 가 돌려준 ID 로 switch_vault/forget_vault 를 할 수 있도록 고정값을 씀
+const LEGACY_VAULT_ID: &str = "default";
+
+// 등록된 vault 하나 (path 는 마스터 키가 암호화되어 저장된 파일)
+#[derive(Clone, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VaultEntry {
+    pub id: String,
+    pub name: String,
+    pub path: String,
+}
+
+// 프론트엔드에 보여줄 vault 목록. USB 드라이브처럼 지금은 연결되지 않은 vault 는 exists 가 false
+#[derive(Serialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VaultInfo {
+    pub id: String,
+    pub name: String,
+    pub path: String,
+    pub exists: bool,
+    pub selected: bool,
+}
+
+/******************* vault 목록 (설정 폴더의 vaults.json) ******************/
+// 목록 파일이 없으면 기존 위치(설정 폴더의 vault.key)를 "Default" 로 등록한 목록으로 시작하므로
+// 이전 버전에서 만든 vault 도 그대로 열 수 있음
+#[derive(Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct VaultRegistry {
+    selected: Option<String>,
+    vaults: Vec<VaultEntry>,
+    #[serde(skip)]
+    file: PathBuf,
+}
+
+impl VaultRegistry {
+    pub fn load(config_dir: &Path) -> Result<Self, String> {
+        let file = config_dir.join("vaults.json");
+        if !file.exists() {
+            let default = VaultEntry {
+                id: LEGACY_VAULT_ID.to_string(),
+                name: "Default".to_string(),
+                path: config_dir.join("vault.key").to_string_lossy().to_string(),
+            };
+            return Ok(VaultRegistry { selected: Some(default.id.clone()), vaults: vec![default], file });
+        }
+        let data = fs::read(&file).map_err(|e| e.to_string())?;
+        let mut registry: VaultRegistry =
+            serde_json::from_slice(&data).map_err(|e| format!("Vault list is damaged: {}", e))?;
+        registry.file = file;
+        Ok(registry)
+    }
+
+    pub fn save(&self) -> Result<(), String> {
+        let data = serde_json::to_vec_pretty(self).map_err(|e| e.to_string())?;
+        progress::write_with_progress(&self.file, &data, &mut |_| {})
+    }
+
+    pub fn selected(&self) -> Option<&VaultEntry> {
+        let id = self.selected.as_ref()?;
+        self.vaults.iter().find(|v| &v.id == id)
+    }
+
+    pub fn list(&self) -> Vec<VaultInfo> {
+        self.vaults
+            .iter()
+            .map(|v| VaultInfo {
+                id: v.id.clone(),
+                name: v.name.clone(),
+                path: v.path.clone(),
+                exists: Path::new(&v.path).is_file(),
+                selected: self.selected.as_ref() == Some(&v.id),
+            })
+            .collect()
+    }
+
+    // 이미 등록된 경로면 새로 추가하지 않고 그 vault 를 선택함
+    pub fn add(&mut self, name: Option<String>, path: &Path) -> String {
+        let path_text = path.to_string_lossy().to_string();
+        if let Some(existing) = self.vaults.iter().find(|v| Path::new(&v.path) == path) {
+            let id = existing.id.clone();
+            self.selected = Some(id.clone());
+            return id;
+        }
+        let name = name.filter(|n| !n.trim().is_empty()).unwrap_or_else(|| {
+            path.file_stem().map(|s| s.to_string_lossy().to_string()).unwrap_or_else(|| path_text.clone())
+        });
+        let id = Uuid::new_v4().to_string();
+        self.vaults.push(VaultEntry { id: id.clone(), name, path: path_text });
+        self.selected = Some(id.clone());
+        id
+    }
+
+    pub fn select(&mut self, id: &str) -> Result<(), String> {
+        if !self.vaults.iter().any(|v| v.id == id) {
+            return Err("Vault not found".into());
+        }
+        self.selected = Some(id.to_string());
+        Ok(())
+    }
+
+    // 목록에서만 지우고 파일은 그대로 둠. 선택된 vault 였다면 남은 것 중 첫 번째를 선택
+    pub fn forget(&mut self, id: &str) -> Result<(), String> {
+        let index = self.vaults.iter().position(|v| v.id == id).ok_or("Vault not found")?;
+        self.vaults.remove(index);
+        if self.selected.as_deref() == Some(id) {
+            self.selected = self.vaults.first().map(|v| v.id.clone());
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn legacy_vault_id_is_stable_before_the_list_is_saved() {
+        let config_dir = std::env::temp_dir().join(format!("vaults-test-{}", std::process::id()));
+        let first = VaultRegistry::load(&config_dir).unwrap();
+        let id = first.selected().unwrap().id.clone();
+        let mut second = VaultRegistry::load(&config_dir).unwrap();
+        assert!(second.select(&id).is_ok());
+        assert!(second.forget(&id).is_ok());
+    }
+}
@@ -0,0 +1,436 @@
+use rand::rngs::StdRng;
+use rand::{Rng, RngCore, SeedableRng};
+use serde::{Deserialize, Serialize};
+use std::fs::{self, OpenOptions};
+use std::io::{Read, Seek, SeekFrom, Write};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use walkdir::WalkDir;
+
+use crate::progress;
+use crate::storage::{self, StorageInfo, StorageProbe};
+
+const CHUNK_SIZE: usize = 1024 * 1024;
+// 삭제 전에 파일/폴더 이름을 무작위 이름으로 바꾸는 횟수
+const RENAME_PASSES: usize = 3;
+
+// 보안 삭제 시 사용할 덮어쓰기 방식
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum WipeScheme {
+    // 0x00 으로 1회 덮어쓰기
+    Zero,
+    // 무작위 데이터로 1회 덮어쓰기 (기존 동작)
+    #[default]
+    Random,
+    // DoD 5220.22-M 방식: 0x00 -> 0xFF -> 무작위 데이터 순으로 3회
+    Dod3Pass,
+    // 지정한 횟수만큼 무작위 데이터로 덮어쓰기
+    Custom { passes: u32 },
+}
+
+// 한 번의 덮어쓰기에 사용할 패턴
+#[derive(Clone, Copy)]
+enum WipePass {
+    Fill(u8),
+    Random,
+}
+
+impl WipeScheme {
+    fn passes(&self) -> Vec<WipePass> {
+        match self {
+            WipeScheme::Zero => vec![WipePass::Fill(0x00)],
+            WipeScheme::Random => vec![WipePass::Random],
+            WipeScheme::Dod3Pass => vec![WipePass::Fill(0x00), WipePass::Fill(0xFF), WipePass::Random],
+            WipeScheme::Custom { passes } => vec![WipePass::Random; (*passes).max(1) as usize],
+        }
+    }
+
+    // 결과 화면에 표시할 방식 이름
+    pub fn label(&self) -> String {
+        match self {
+            WipeScheme::Zero => "Zero (1 pass)".to_string(),
+            WipeScheme::Random => "Random (1 pass)".to_string(),
+            WipeScheme::Dod3Pass => "DoD 5220.22-M (3 passes)".to_string(),
+            WipeScheme::Custom { passes } => format!("Custom ({} passes)", (*passes).max(1)),
+        }
+    }
+}
+
+// 하드 링크(nlink > 1)가 걸린 파일의 처리 방식
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum HardlinkPolicy {
+    // 삭제해도 다른 이름이 남으므로 덮어쓰지 않고 건너뜀
+    #[default]
+    Refuse,
+    // 덮어쓰고 이 이름은 삭제하되, 다른 이름이 남아 있음을 결과에 표시
+    Warn,
+}
+
+// 파일 내용을 없애는 방법. SSD 나 Copy-on-Write 파일 시스템에서는 덮어쓰기 대신 블록 해제가 더 효과적일 수 있음
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum WipeStrategy {
+    // scheme 에 따라 제자리 덮어쓰기
+    #[default]
+    Overwrite,
+    // 덮어쓰지 않고 블록만 해제 (hole punching, discard 마운트 시 TRIM 전달)
+    PunchHole,
+    // 덮어쓴 뒤 블록도 해제
+    OverwriteAndPunch,
+}
+
+// 보안 삭제 옵션. 프론트엔드에서 생략하면 기존과 같은 무작위 1회 덮어쓰기로 동작
+#[derive(Clone, Copy, Debug, Default, Deserialize)]
+#[serde(rename_all = "camelCase", default)]
+pub struct WipeOptions {
+    pub scheme: WipeScheme,
+    // 마지막 덮어쓰기 결과를 다시 읽어서 검증할지 여부
+    pub verify: bool,
+    pub hardlinks: HardlinkPolicy,
+    pub strategy: WipeStrategy,
+}
+
+/******************* 파일 하나를 덮어쓰고, 길이를 0으로 자른 뒤 삭제 ******************/
+// on_progress 에는 모든 패스(검증 포함) 중 끝난 비율을 파일 크기 기준의 바이트 수로 환산해서 전달
+pub fn wipe_file(
+    path: &Path,
+    options: &WipeOptions,
+    cancel_flag: &AtomicBool,
+    on_progress: &mut dyn FnMut(u64),
+) -> Result<(), String> {
+    // 링크를 따라가면 다른 위치의 파일을 덮어쓰게 되므로 링크 자체의 정보를 확인
+    let metadata = fs::symlink_metadata(path).map_err(|e| e.to_string())?;
+    if !metadata.is_file() {
+        return Err("Refused: not a regular file".to_string());
+    }
+    let file_size = metadata.len();
+    let mut file = open_no_follow(path)?;
+    let mut buffer = vec![0u8; CHUNK_SIZE];
+
+    let passes = match options.strategy {
+        WipeStrategy::PunchHole => Vec::new(),
+        WipeStrategy::Overwrite | WipeStrategy::OverwriteAndPunch => options.scheme.passes(),
+    };
+
+    // 패스 수와 검증 여부에 따라 전체 작업량이 달라지므로 파일 크기 기준으로 환산
+    let total_work = file_size * (passes.len() as u64 + u64::from(options.verify && !passes.is_empty()));
+    let mut done_work = 0u64;
+    let mut report = |done_work: u64| on_progress(progress::scale(done_work, total_work, file_size));
+
+    // 검증을 위해 마지막 무작위 패스의 시드를 기억해 둠
+    let mut last_pass = None;
+    for pass in passes {
+        let seed = match pass {
+            WipePass::Fill(byte) => {
+                buffer.fill(byte);
+                None
+            }
+            WipePass::Random => {
+                let mut seed = [0u8; 32];
+                rand::rng().fill_bytes(&mut seed);
+                Some(seed)
+            }
+        };
+        let mut rng = seed.map(StdRng::from_seed);
+
+        file.seek(SeekFrom::Start(0)).map_err(|e| e.to_string())?;
+        let mut written_bytes = 0u64;
+        while written_bytes < file_size {
+            if cancel_flag.load(Ordering::SeqCst) {
+                // 덮어쓰기 중단 시 파일 삭제하지 않음
+                return Err("Operation cancelled.".to_string());
+            }
+            if let Some(rng) = rng.as_mut() {
+                rng.fill_bytes(&mut buffer);
+            }
+            let bytes_to_write = std::cmp::min(file_size - written_bytes, CHUNK_SIZE as u64) as usize;
+            file.write_all(&buffer[..bytes_to_write]).map_err(|e| e.to_string())?;
+            written_bytes += bytes_to_write as u64;
+            done_work += bytes_to_write as u64;
+            report(done_work);
+        }
+        file.sync_all().map_err(|e| e.to_string())?;
+        last_pass = Some((pass, seed));
+    }
+
+    if options.verify {
+        if let Some((pass, seed)) = last_pass {
+            verify_pass(&mut file, file_size, pass, seed, cancel_flag, &mut |read_bytes| report(done_work + read_bytes))?;
+        }
+    }
+
+    if options.strategy != WipeStrategy::Overwrite {
+        storage::punch_hole(&file, file_size)?;
+        file.sync_all().map_err(|e| e.to_string())?;
+    }
+
+    // 삭제 전에 길이를 0으로 잘라서 원래 크기 정보도 남기지 않음
+    file.set_len(0).map_err(|e| e.to_string())?;
+    file.sync_all().map_err(|e| e.to_string())?;
+    drop(file);
+    remove_file_scrubbed(path)
+}
+
+/******************* 이름을 여러 번 무작위로 바꾼 뒤 파일 삭제 ******************/
+// 디렉터리 엔트리나 저널에 원래 파일 이름이 남지 않도록 함
+pub fn remove_file_scrubbed(path: &Path) -> Result<(), String> {
+    let scrubbed_path = scrub_name(path)?;
+    fs::remove_file(&scrubbed_path).map_err(|e| e.to_string())?;
+    sync_parent_dir(&scrubbed_path);
+    Ok(())
+}
+
+/******************* 이름을 여러 번 무작위로 바꾼 뒤 빈 폴더 삭제 ******************/
+pub fn remove_dir_scrubbed(path: &Path) -> Result<(), String> {
+    // 남은 항목이 있으면 이름을 바꾸기 전에 실패시켜서 폴더가 무작위 이름으로 남지 않게 함
+    if fs::read_dir(path).map_err(|e| e.to_string())?.next().is_some() {
+        return Err("Directory not empty: some entries were skipped or failed".to_string());
+    }
+    let scrubbed_path = scrub_name(path)?;
+    fs::remove_dir(&scrubbed_path).map_err(|e| e.to_string())?;
+    sync_parent_dir(&scrubbed_path);
+    Ok(())
+}
+
+// 같은 폴더 안에서 원래 이름과 같은 길이의 무작위 이름으로 RENAME_PASSES 회 변경하고 최종 경로를 반환
+fn scrub_name(path: &Path) -> Result<PathBuf, String> {
+    let parent = path.parent().ok_or("Invalid path: no parent directory")?;
+    let name_len = path.file_name().map(|n| n.len()).unwrap_or(1).max(1);
+
+    let mut current_path = path.to_path_buf();
+    for _ in 0..RENAME_PASSES {
+        let next_path = loop {
+            // 기존 파일을 덮어쓰지 않도록 존재하지 않는 이름을 고름
+            let candidate = parent.join(random_name(name_len));
+            if fs::symlink_metadata(&candidate).is_err() {
+                break candidate;
+            }
+        };
+        fs::rename(&current_path, &next_path).map_err(|e| e.to_string())?;
+        sync_parent_dir(&next_path);
+        current_path = next_path;
+    }
+    Ok(current_path)
+}
+
+// 대소문자를 구분하지 않는 파일 시스템에서도 충돌하지 않도록 소문자와 숫자만 사용
+fn random_name(len: usize) -> String {
+    const CHARSET: &[u8] = b"abcdefghijklmnopqrstuvwxyz0123456789";
+    let mut rng = rand::rng();
+    (0..len).map(|_| CHARSET[rng.random_range(0..CHARSET.len())] as char).collect()
+}
+
+// 이름 변경/삭제가 디스크에 반영되도록 상위 폴더를 동기화 (Windows 에서는 폴더를 열 수 없어 생략)
+fn sync_parent_dir(path: &Path) {
+    #[cfg(unix)]
+    if let Some(parent) = path.parent() {
+        if let Ok(dir) = fs::File::open(parent) {
+            let _ = dir.sync_all();
+        }
+    }
+    #[cfg(not(unix))]
+    let _ = path;
+}
+
+// 확인 후 여는 사이에 링크로 바뀌더라도 따라가지 않도록 O_NOFOLLOW 로 염
+fn open_no_follow(path: &Path) -> Result<fs::File, String> {
+    let mut open_options = OpenOptions::new();
+    open_options.read(true).write(true);
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::OpenOptionsExt;
+        open_options.custom_flags(libc::O_NOFOLLOW);
+    }
+    open_options.open(path).map_err(|e| e.to_string())
+}
+
+// 마지막 패스가 디스크에 그대로 기록되었는지 다시 읽어서 비교
+fn verify_pass(
+    file: &mut fs::File,
+    file_size: u64,
+    pass: WipePass,
+    seed: Option<[u8; 32]>,
+    cancel_flag: &AtomicBool,
+    on_progress: &mut dyn FnMut(u64),
+) -> Result<(), String> {
+    let mut expected = vec![0u8; CHUNK_SIZE];
+    let mut actual = vec![0u8; CHUNK_SIZE];
+    let mut rng = seed.map(StdRng::from_seed);
+    if let WipePass::Fill(byte) = pass {
+        expected.fill(byte);
+    }
+
+    file.seek(SeekFrom::Start(0)).map_err(|e| e.to_string())?;
+    let mut read_bytes = 0u64;
+    while read_bytes < file_size {
+        if cancel_flag.load(Ordering::SeqCst) {
+            return Err("Operation cancelled.".to_string());
+        }
+        if let Some(rng) = rng.as_mut() {
+            rng.fill_bytes(&mut expected);
+        }
+        let bytes_to_read = std::cmp::min(file_size - read_bytes, CHUNK_SIZE as u64) as usize;
+        file.read_exact(&mut actual[..bytes_to_read]).map_err(|e| e.to_string())?;
+        if actual[..bytes_to_read] != expected[..bytes_to_read] {
+            return Err(format!("Verification failed at offset {}", read_bytes));
+        }
+        read_bytes += bytes_to_read as u64;
+        on_progress(read_bytes);
+    }
+    Ok(())
+}
+
+// 보안 삭제 시 처리할 항목의 종류
+#[derive(Clone, Copy, PartialEq, Eq)]
+pub enum TargetKind {
+    File,
+    Symlink,
+    Dir,
+    // FIFO, 소켓, 장치 파일 등 덮어쓸 수 없는 항목 (종류 이름 포함)
+    Special(&'static str),
+}
+
+pub struct WipeTarget {
+    pub path: PathBuf,
+    pub kind: TargetKind,
+    pub size: u64,
+    pub links: u64,
+    pub device: Option<u64>,
+}
+
+// 항목 하나를 처리한 결과에 함께 표시할 내용
+#[derive(Default)]
+pub struct WipeOutcome {
+    pub note: Option<String>,
+    pub storage: Option<StorageInfo>,
+}
+
+/******************* 링크를 따라가지 않은 파일 종류로 항목 분류 ******************/
+pub fn classify(file_type: &fs::FileType) -> TargetKind {
+    if file_type.is_symlink() {
+        return TargetKind::Symlink;
+    }
+    if file_type.is_dir() {
+        return TargetKind::Dir;
+    }
+    if file_type.is_file() {
+        return TargetKind::File;
+    }
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::FileTypeExt;
+        if file_type.is_fifo() {
+            return TargetKind::Special("FIFO");
+        }
+        if file_type.is_socket() {
+            return TargetKind::Special("socket");
+        }
+        if file_type.is_block_device() || file_type.is_char_device() {
+            return TargetKind::Special("device file");
+        }
+    }
+    TargetKind::Special("unsupported file type")
+}
+
+// 파일에 연결된 이름(하드 링크)의 수. 확인할 수 없는 플랫폼에서는 1로 간주
+pub fn link_count(metadata: &fs::Metadata) -> u64 {
+    #[cfg(unix)]
+    {
+        use std::os::unix::fs::MetadataExt;
+        metadata.nlink()
+    }
+    #[cfg(not(unix))]
+    {
+        let _ = metadata;
+        1
+    }
+}
+
+/******************* 삭제할 경로들을 삭제 순서(하위 항목 먼저)대로 수집 ******************/
+// 폴더가 주어지면 안의 모든 항목과 폴더 자신을 포함하고, 탐색 중 발생한 오류는 (경로, 오류 메시지) 목록으로 함께 반환
+pub fn collect_targets(paths: &[String]) -> (Vec<WipeTarget>, Vec<(String, String)>) {
+    let mut targets = Vec::new();
+    let mut errors = Vec::new();
+
+    for root in paths {
+        // contents_first: 폴더는 안의 내용이 모두 나온 뒤에 나오므로 아래에서 위로 지울 수 있음
+        // follow_root_links(false): 선택한 경로 자체가 링크여도 대상 폴더로 들어가지 않음
+        for entry in WalkDir::new(root).contents_first(true).follow_root_links(false) {
+            match entry {
+                Ok(entry) => {
+                    let kind = classify(&entry.file_type());
+                    let (size, links, device) = match (kind, entry.metadata()) {
+                        (TargetKind::File, Ok(metadata)) => {
+                            (metadata.len(), link_count(&metadata), storage::device_id(&metadata))
+                        }
+                        _ => (0, 1, None),
+                    };
+                    targets.push(WipeTarget { path: entry.into_path(), kind, size, links, device });
+                }
+                Err(e) => {
+                    let path = e.path().map(|p| p.to_string_lossy().to_string()).unwrap_or_else(|| root.clone());
+                    errors.push((path, e.to_string()));
+                }
+            }
+        }
+    }
+    (targets, errors)
+}
+
+/******************* 수집된 항목 하나를 종류에 맞게 삭제 ******************/
+// 파일이면 놓인 저장 장치를 확인해서, 덮어쓰기를 신뢰할 수 있는 정도와 경고를 결과에 함께 담음
+pub fn wipe_target(
+    target: &WipeTarget,
+    options: &WipeOptions,
+    probe: &mut StorageProbe,
+    cancel_flag: &AtomicBool,
+    on_progress: &mut dyn FnMut(u64),
+) -> Result<WipeOutcome, String> {
+    match target.kind {
+        TargetKind::File => {
+            let mut notes = Vec::new();
+            if target.links > 1 {
+                match options.hardlinks {
+                    HardlinkPolicy::Refuse => {
+                        return Err(format!(
+                            "Skipped: file has {} hard links, other names would remain after deletion",
+                            target.links
+                        ));
+                    }
+                    HardlinkPolicy::Warn => notes.push(format!(
+                        "Contents wiped, but {} other hard link(s) still exist",
+                        target.links - 1
+                    )),
+                }
+            }
+
+            let storage = probe.detect(&target.path, target.device);
+            wipe_file(&target.path, options, cancel_flag, on_progress)?;
+
+            // 블록 해제까지 했다면 덮어쓰기만으로 부족하다는 경고는 생략
+            if options.strategy == WipeStrategy::Overwrite {
+                if let Some(warning) = &storage.warning {
+                    notes.push(match &storage.filesystem {
+                        Some(filesystem) => format!("{}: {}", filesystem, warning),
+                        None => warning.clone(),
+                    });
+                }
+            }
+            let note = (!notes.is_empty()).then(|| notes.join(" / "));
+            Ok(WipeOutcome { note, storage: Some(storage) })
+        }
+        // 링크가 가리키는 대상은 건드리지 않고 링크만 제거
+        TargetKind::Symlink => {
+            remove_file_scrubbed(&target.path)?;
+            Ok(WipeOutcome {
+                note: Some("Symbolic link removed, target left untouched".to_string()),
+                storage: None,
+            })
+        }
+        // 하위 항목 삭제에 실패했거나 건너뛴 항목이 있으면 폴더가 비어 있지 않으므로 여기서 함께 실패함
+        TargetKind::Dir => remove_dir_scrubbed(&target.path).map(|_| WipeOutcome::default()),
+        TargetKind::Special(kind) => Err(format!("Skipped: {}", kind)),
+    }
+}
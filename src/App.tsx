@@ -1,30 +1,79 @@
 import React, { useEffect, useState } from "react";
-import { invoke } from "@tauri-apps/api/core";
-import { open, message, ask } from "@tauri-apps/plugin-dialog";
-import { listen } from "@tauri-apps/api/event";
+import { invoke, Channel } from "@tauri-apps/api/core";
+import { open, save, message, ask } from "@tauri-apps/plugin-dialog";
 import { useTranslation } from "react-i18next";
 
-import { Page, StagedFile } from "./types";
+import { EncryptOptions, Page, StagedFile } from "./types";
 import Header from "./components/Header";
 import Sidebar from "./components/Sidebar";
 import Settings from "./components/Settings";
+import AuditLog from "./components/AuditLog";
+import Keys from "./components/Keys";
+import Mirror from "./components/Mirror";
 import Unlock from "./components/Unlock";
 import Setup from "./components/Setup";
 import ProgressDialog, { EachFile, Status } from "./components/ProgressDialog";
 import AppEachContent from "./components/AppEachContent";
+import PasswordPrompt from "./components/PasswordPrompt";
 import "./App.css";
 
-interface ProgressPayload {
-  status: string;
-  current_file_path: string;
-  number_of_files: number;
-  current_file_number: number;
-  total_progress: number;
-  total_files: string[];
-  suceeded_files: EachFile[];
-  failed_files?: EachFile[];
+// 작업 하나의 진행 상황 메시지 (백엔드 job::JobEvent)
+type JobEvent =
+  | { event: "started"; data: { jobId: string; numberOfFiles: number; totalBytes: number } }
+  | { event: "fileStarted"; data: { path: string; fileNumber: number; size: number } }
+  | { event: "fileFinished"; data: { file: EachFile } }
+  | {
+      event: "progress";
+      data: {
+        currentFilePath: string;
+        currentFileNumber: number;
+        numberOfFiles: number;
+        totalProgress: number;
+        processedBytes: number;
+        totalBytes: number;
+        bytesPerSecond: number;
+        etaSeconds: number | null;
+      };
+    }
+  | {
+      event: "cancelled";
+      data: {
+        jobId: string;
+        succeeded: string[];
+        failed: string[];
+        notStarted: string[];
+        outputCount: number;
+      };
+    }
+  | { event: "rolledBack"; data: { reason: string; discarded: string[]; notStarted: string[] } }
+  | { event: "finished"; data: { succeeded: number; failed: number; processedBytes: number } };
+
+interface ScanEntry {
+  path: string;
+  reason: string;
+}
+
+interface ScanBatch {
+  status: "SCANNING" | "DONE" | "CANCELLED";
+  files: string[];
+  skipped: ScanEntry[];
+  errors: ScanEntry[];
+  notes: ScanEntry[];
+  fileCount: number;
+  skippedCount: number;
+  errorCount: number;
+  noteCount: number;
+  totalBytes: number;
 }
 
+// 폴더 추가 시 기본으로 제외할 항목 (버전 관리/패키지 폴더, OS 가 만드는 파일, 숨김 파일)
+// 암호화/복호화는 원본을 지우지 않으므로 하드 링크가 걸린 파일도 포함하고 알림만 남김 (생략하면 보안 삭제처럼 건너뜀)
+const DEFAULT_SCAN_OPTIONS = {
+  exclude: [".git", "node_modules", ".DS_Store", "Thumbs.db", "desktop.ini"],
+  includeHidden: false,
+  hardlinks: "warn",
+};
+
 interface ProgressState {
   isVisible: boolean;
   status: Status;
@@ -32,12 +81,41 @@ interface ProgressState {
   numberOfFiles: number;
   currentFileNumber: number;
   totalProgress: number;
+  processedBytes: number;
+  totalBytes: number;
+  bytesPerSecond: number;
+  etaSeconds: number | null;
 }
 
+const EMPTY_BYTE_PROGRESS = { processedBytes: 0, totalBytes: 0, bytesPerSecond: 0, etaSeconds: null };
+
 interface FileStatus {
+  jobId?: string; // 보고서를 내보낼 때 사용
   totalFiles: string[];
   suceededFiles: EachFile[];
   failedFiles?: EachFile[] | undefined;
+  cancelled?: CancelledJob;
+  rolledBack?: RolledBackJob;
+}
+
+// 잠금 해제에 성공했을 때 받는 지난 실패 기록 (백엔드 throttle::UnlockSummary)
+interface UnlockSummary {
+  failedAttempts: number;
+  lastFailedAt: string | null;
+  warnings: string[];
+}
+
+// 취소된 작업의 시작하지 못한 항목과, 아직 지우지 않은 출력 파일 수
+export interface CancelledJob {
+  jobId: string;
+  notStarted: string[];
+  outputCount: number;
+}
+
+// 트랜잭션 모드에서 결과를 반영하지 않은 이유와 처리하지 않은 항목
+export interface RolledBackJob {
+  reason: string;
+  notStarted: string[];
 }
 
 const App: React.FC = () => {
@@ -55,6 +133,7 @@ const App: React.FC = () => {
     numberOfFiles: 0,
     currentFileNumber: 0,
     totalProgress: 0,
+    ...EMPTY_BYTE_PROGRESS,
   });
 
   const [fileStatus, setFileStatus] = useState<FileStatus>({
@@ -64,22 +143,78 @@ const App: React.FC = () => {
   });
 
   const [isCancelling, setIsCancelling] = useState(false); // 취소 상태를 관리
+  const [transactional, setTransactional] = useState(false); // 모두 성공했을 때만 결과를 저장
+  const [encryptOptions, setEncryptOptions] = useState<EncryptOptions>({
+    format: "SECURE_VAULT",
+    mode: "VAULT",
+    recipients: [],
+    password: "",
+    confirm: "",
+    cipher: null,
+  });
+  // 비밀번호로 암호화된 파일을 복호화하기 전에 비밀번호를 물어보는 동안 보관하는 작업
+  const [pendingDecrypt, setPendingDecrypt] = useState<{ files: string[]; destDir: string } | null>(null);
+
+  const [newVaultPath, setNewVaultPath] = useState<string | undefined>(); // 새로 만들 vault 위치
+  const [restoreSource, setRestoreSource] = useState<string | undefined>(); // 복원할 헤더 파일
+  const [vaultVersion, setVaultVersion] = useState(0); // vault 를 바꿀 때마다 잠금/설정 화면을 새로 그림
 
   const handleLock = () => {
+    invoke("lock_vault").catch(console.error);
     setVaultState("locked");
   };
 
+  // 선택된 vault 가 바뀌면 파일이 있는지 다시 확인
+  const refreshVaultState = () => {
+    setNewVaultPath(undefined);
+    setRestoreSource(undefined);
+    setVaultVersion((prev) => prev + 1);
+    invoke<boolean>("vault_exists")
+      .then((exists) => setVaultState(exists ? "locked" : "needs_setup"))
+      .catch(console.error);
+  };
+
+  const handleCreateNewVault = (path: string) => {
+    setNewVaultPath(path);
+    setVaultState("needs_setup");
+  };
+
+  const handleRestoreVault = (source: string) => {
+    setRestoreSource(source);
+    setVaultState("needs_setup");
+  };
+
   const handleUnlock = async (password: string) => {
     try {
-      await invoke("unlock_vault", { password });
+      const summary = await invoke<UnlockSummary>("unlock_vault", { password });
       setVaultState("unlocked");
+      // 지난 잠금 해제 이후 실패한 시도가 있었으면 알려줌
+      if (summary.failedAttempts > 0) {
+        await message(
+          t("messages.failedUnlockAttempts", {
+            count: summary.failedAttempts,
+            time: summary.lastFailedAt ? new Date(summary.lastFailedAt).toLocaleString() : "",
+          })
+        );
+      }
+      // 잠금 해제는 되었지만 실패 기록이나 감사 로그 등을 저장하지 못한 경우
+      if (summary.warnings.length > 0) {
+        await message(t("messages.unlockWarnings", { details: summary.warnings.join("\n") }));
+      }
     } catch (e) {
+      // 실패 횟수 제한으로 vault 가 삭제된 경우 새로 만들도록 안내
+      if (!(await invoke<boolean>("vault_exists"))) {
+        await message(t("messages.vaultErased"));
+        setVaultState("needs_setup");
+      }
       throw e;
     }
   };
 
   // 설정 완료 핸들러
   const handleSetupSuccess = () => {
+    setNewVaultPath(undefined);
+    setRestoreSource(undefined);
     setVaultState("unlocked");
   };
 
@@ -105,29 +240,41 @@ const App: React.FC = () => {
         title: t("instructions.selectFolder"),
       });
       if (typeof selected === "string") {
-        let filesInDir = await invoke<string[]>("get_files_in_dir_recursive", {
-          dirPath: selected,
-        });
-
-        if (filesInDir.length === 0) {
-          await message(t("error.noFilesDesc"), {
-            title: t("error.noFilesTitle"),
-          });
+        // 보안 삭제 탭에서는 폴더 자체를 추가하여 안의 파일과 폴더 이름까지 함께 삭제
+        if (activePage === "delete") {
+          handleFilesAdded([selected]);
           return;
         }
 
-        // 복호화 탭일 경우 .enc 파일만 필터링
-        if (activePage === "decrypt") {
-          filesInDir = filesInDir.filter((file) => file.endsWith(".enc"));
-          if (filesInDir.length === 0) {
-            await message(t("error.noEncFiles"), {
-              title: t("error.noFilesTitle"),
-            });
-            return;
+        // 복호화 탭일 경우 .enc 파일만 탐색
+        const options =
+          activePage === "decrypt"
+            ? { ...DEFAULT_SCAN_OPTIONS, include: ["*.enc"] }
+            : DEFAULT_SCAN_OPTIONS;
+
+        // 찾은 파일을 묶음 단위로 받아서 바로 목록에 추가
+        const handleBatch = (batch: ScanBatch) => {
+          if (batch.files.length > 0) {
+            handleFilesAdded(batch.files);
           }
-        }
+          if (batch.errors.length > 0) {
+            console.warn("[scan_folder] unreadable entries :", batch.errors);
+          }
+          if (batch.notes.length > 0) {
+            console.warn("[scan_folder] hard-linked files :", batch.notes);
+          }
+        };
+        const onBatch = new Channel<ScanBatch>();
+        onBatch.onmessage = handleBatch;
+
+        const summary = await invoke<ScanBatch>("scan_folder", { dirPath: selected, options, onBatch });
+        handleBatch(summary);
 
-        handleFilesAdded(filesInDir);
+        if (summary.status === "DONE" && summary.fileCount === 0) {
+          await message(t(activePage === "decrypt" ? "error.noEncFiles" : "error.noFilesDesc"), {
+            title: t("error.noFilesTitle"),
+          });
+        }
       }
     } catch (error) {
       console.error(error);
@@ -160,14 +307,124 @@ const App: React.FC = () => {
       numberOfFiles,
       currentFileNumber: 1,
       totalProgress: 0,
+      ...EMPTY_BYTE_PROGRESS,
     });
   };
 
+  // 작업 하나의 진행 상황과 항목별 결과를 받을 채널 생성
+  const createJobChannel = () => {
+    const onEvent = new Channel<JobEvent>();
+    const totalFiles: string[] = [];
+    const suceededFiles: EachFile[] = [];
+    const failedFiles: EachFile[] = [];
+    let rolledBack: RolledBackJob | undefined;
+    let jobId: string | undefined;
+
+    onEvent.onmessage = (jobEvent) => {
+      switch (jobEvent.event) {
+        case "started":
+          jobId = jobEvent.data.jobId;
+          setProgress((prev) => ({
+            ...prev,
+            numberOfFiles: jobEvent.data.numberOfFiles,
+            totalBytes: jobEvent.data.totalBytes,
+          }));
+          break;
+        case "fileStarted":
+          setProgress((prev) => ({
+            ...prev,
+            currentFile: jobEvent.data.path,
+            currentFileNumber: jobEvent.data.fileNumber,
+          }));
+          break;
+        case "fileFinished":
+          totalFiles.push(jobEvent.data.file.path);
+          (jobEvent.data.file.error ? failedFiles : suceededFiles).push(jobEvent.data.file);
+          break;
+        case "progress":
+          setProgress({
+            isVisible: true,
+            status: Status.PROCESSING,
+            currentFile: jobEvent.data.currentFilePath,
+            numberOfFiles: jobEvent.data.numberOfFiles,
+            currentFileNumber: jobEvent.data.currentFileNumber,
+            totalProgress: jobEvent.data.totalProgress * 100,
+            processedBytes: jobEvent.data.processedBytes,
+            totalBytes: jobEvent.data.totalBytes,
+            bytesPerSecond: jobEvent.data.bytesPerSecond,
+            etaSeconds: jobEvent.data.etaSeconds,
+          });
+          break;
+        case "finished":
+          setProgress((prev) => ({
+            ...prev,
+            totalProgress: 100,
+            processedBytes: prev.totalBytes,
+            etaSeconds: 0,
+          }));
+          setFileStatus({ jobId, totalFiles, suceededFiles, failedFiles, rolledBack });
+
+          // 100% 진행도를 잠시 보여준 후 완료 화면 표시해 주기
+          setTimeout(() => {
+            setProgress((prev) => ({
+              ...prev,
+              status: Status.DONE,
+              totalProgress: 100,
+            }));
+          }, 1500);
+          break;
+        // 성공했지만 반영되지 않은 항목은 실패 목록으로 옮김
+        case "rolledBack": {
+          const discarded = new Set(jobEvent.data.discarded);
+          for (let i = suceededFiles.length - 1; i >= 0; i--) {
+            if (discarded.has(suceededFiles[i].path)) {
+              failedFiles.push({ ...suceededFiles[i], error: t("results.discarded") });
+              suceededFiles.splice(i, 1);
+            }
+          }
+          rolledBack = { reason: jobEvent.data.reason, notStarted: jobEvent.data.notStarted };
+          break;
+        }
+        // 취소 시점까지의 결과와 시작하지 못한 항목을 결과 화면에 표시
+        case "cancelled":
+          setFileStatus({
+            jobId,
+            totalFiles,
+            suceededFiles,
+            failedFiles,
+            rolledBack,
+            cancelled: {
+              jobId: jobEvent.data.jobId,
+              notStarted: jobEvent.data.notStarted,
+              outputCount: jobEvent.data.outputCount,
+            },
+          });
+          setProgress((prev) => ({ ...prev, status: Status.CANCELLED }));
+          break;
+      }
+    };
+    return onEvent;
+  };
+
   const handleEncrypt = async () => {
     if (stagedFiles.length === 0) {
       await message(t("error.noFilesEcrypt"), { title: t("error.noFilesTitle") });
       return;
     }
+    const { format, mode, recipients, password, confirm, cipher } = encryptOptions;
+    if (mode === "RECIPIENTS" && recipients.length === 0) {
+      await message(t("error.noRecipients"));
+      return;
+    }
+    const usePassword = mode === "PASSWORD";
+    if (usePassword && password.length < 8) {
+      await message(t("error.passwordTooShort"));
+      return;
+    }
+    if (usePassword && password !== confirm) {
+      await message(t("error.passwordsNoMatch"));
+      return;
+    }
     try {
       const destDir = await open({
         title: t("instructions.selectFolderForSave"),
@@ -177,8 +434,18 @@ const App: React.FC = () => {
 
       const filePaths = stagedFiles.map((f) => f.path);
       startOperation(filePaths[0], filePaths.length);
-      await invoke("encrypt_files", { files: filePaths, destinationDir: destDir });
+      await invoke("encrypt_files", {
+        files: filePaths,
+        destinationDir: destDir,
+        transactional,
+        recipients: mode === "RECIPIENTS" ? recipients : undefined,
+        format,
+        password: usePassword ? password : undefined,
+        cipher: cipher ?? undefined,
+        onEvent: createJobChannel(),
+      });
       setStagedFiles([]);
+      setEncryptOptions((prev) => ({ ...prev, password: "", confirm: "" }));
     } catch (error) {
       console.error(error);
       await message(t("error.operationFailed"));
@@ -198,8 +465,28 @@ const App: React.FC = () => {
       if (typeof destDir !== "string") return;
 
       const filePaths = stagedFiles.map((f) => f.path);
+      // 비밀번호로 암호화된 파일이 있으면 비밀번호를 입력받은 뒤 시작
+      if (await invoke<boolean>("files_need_password", { files: filePaths })) {
+        setPendingDecrypt({ files: filePaths, destDir });
+        return;
+      }
+      await runDecrypt(filePaths, destDir);
+    } catch (error) {
+      console.error(error);
+      await message(t("error.operationFailed"));
+    }
+  };
+
+  const runDecrypt = async (filePaths: string[], destDir: string, password?: string) => {
+    try {
       startOperation(filePaths[0], filePaths.length);
-      await invoke("decrypt_files", { files: filePaths, destinationDir: destDir });
+      await invoke("decrypt_files", {
+        files: filePaths,
+        destinationDir: destDir,
+        transactional,
+        password,
+        onEvent: createJobChannel(),
+      });
       setStagedFiles([]);
     } catch (error) {
       console.error(error);
@@ -207,6 +494,12 @@ const App: React.FC = () => {
     }
   };
 
+  const handlePasswordSubmit = (password: string) => {
+    if (!pendingDecrypt) return;
+    setPendingDecrypt(null);
+    runDecrypt(pendingDecrypt.files, pendingDecrypt.destDir, password);
+  };
+
   const handleSecureDelete = async () => {
     if (stagedFiles.length === 0) {
       await message(t("error.noFilesToDelete"), { title: t("error.noFilesTitle") });
@@ -224,7 +517,7 @@ const App: React.FC = () => {
       try {
         // --- 수정: 진행률 표시 시작 및 새로운 커맨드 호출 ---
         startOperation(filePaths[0], filePaths.length);
-        await invoke("secure_delete_files", { files: filePaths });
+        await invoke("secure_delete_files", { files: filePaths, onEvent: createJobChannel() });
         setStagedFiles([]); // 작업 시작 후 목록 비우기
       } catch (error) {
         console.error(error);
@@ -233,6 +526,29 @@ const App: React.FC = () => {
     }
   };
 
+  // 키 교체 후 선택한 폴더의 파일을 새 마스터 키로 옮기는 작업 (설정 페이지에서 시작)
+  const handleRewrapFiles = async () => {
+    try {
+      startOperation("", 0);
+      await invoke("rewrap_files", { onEvent: createJobChannel() });
+    } catch (error) {
+      console.error(error);
+      await message(t("error.operationFailed"));
+    }
+  };
+
+  // 원본 폴더를 대상 폴더에 미러 (바뀐 파일만 암호화하고 지워진 파일의 출력은 삭제)
+  const handleMirror = async (sourceDir: string, destinationDir: string) => {
+    try {
+      startOperation(sourceDir, 0);
+      await invoke("mirror_folder", { sourceDir, destinationDir, onEvent: createJobChannel() });
+    } catch (error) {
+      console.error(error);
+      handleCloseProgress();
+      await message(String(error));
+    }
+  };
+
   // 컨텐츠 유형에 따라 실행할 매서드
   const onButtonClickByType = () => {
     if (activePage === "encrypt") return handleEncrypt();
@@ -241,23 +557,64 @@ const App: React.FC = () => {
     else return;
   };
 
+  // 취소를 요청하면 처리 중인 파일이 끝난 뒤 cancelled 메시지로 결과가 도착함
   const handleCancel = async () => {
-    // 즉시 취소 상태로 변경하여 이후의 이벤트를 차단합니다.
     setIsCancelling(true);
-
     await invoke("cancel_operation");
-    setProgress({
-      isVisible: false,
-      status: Status.PROCESSING,
-      currentFile: "",
-      numberOfFiles: 0,
-      currentFileNumber: 0,
-      totalProgress: 0,
+  };
+
+  // 취소된 작업이 이미 만든 파일 삭제
+  const handleRemoveOutputs = async () => {
+    const cancelled = fileStatus.cancelled;
+    if (!cancelled) return;
+    try {
+      const results = await invoke<EachFile[]>("remove_job_outputs", { jobId: cancelled.jobId });
+      const failed = results.filter((file) => file.error);
+      setFileStatus((prev) => ({
+        ...prev,
+        cancelled: prev.cancelled && { ...prev.cancelled, outputCount: failed.length },
+      }));
+      if (failed.length > 0) {
+        console.error("[remove_job_outputs] failed :", failed);
+        await message(t("messages.removeOutputsFailed", { count: failed.length }));
+      } else {
+        await message(t("messages.removeOutputsSuccess", { count: results.length }));
+      }
+    } catch (error) {
+      console.error(error);
+      await message(t("error.operationFailed"));
+    }
+  };
+
+  // 끝난 작업의 처리 기록을 JSON 또는 CSV 로 저장 (확장자로 형식을 정함)
+  const handleExportReport = async () => {
+    const jobId = fileStatus.jobId;
+    if (!jobId) return;
+    const path = await save({
+      defaultPath: `report-${jobId}.json`,
+      filters: [
+        { name: "JSON", extensions: ["json"] },
+        { name: "CSV", extensions: ["csv"] },
+      ],
     });
-    message(t("messages.cancelSuccess"));
+    if (!path) return;
+    const format = path.toLowerCase().endsWith(".csv") ? "csv" : "json";
+    try {
+      await invoke("export_job_report", { jobId, format, path });
+      await message(t("messages.exportReportSuccess"));
+    } catch (error) {
+      console.error("[export_job_report] failed :", error);
+      await message(t("messages.exportReportFailed"));
+    }
   };
 
   const handleCloseProgress = () => {
+    // 지우지 않고 닫으면 만들어진 파일은 그대로 둠
+    const cancelled = fileStatus.cancelled;
+    if (cancelled && cancelled.outputCount > 0) {
+      invoke("keep_job_outputs", { jobId: cancelled.jobId }).catch(console.error);
+    }
+
     setProgress({
       isVisible: false,
       status: Status.IDLE,
@@ -265,6 +622,7 @@ const App: React.FC = () => {
       numberOfFiles: 0,
       currentFileNumber: 0,
       totalProgress: 0,
+      ...EMPTY_BYTE_PROGRESS,
     });
   };
 
@@ -273,71 +631,37 @@ const App: React.FC = () => {
   }, [activePage]);
 
   useEffect(() => {
-    invoke<boolean>("vault_exists")
-      .then((exists) => setVaultState(exists ? "locked" : "needs_setup"))
-      .catch(console.error);
+    refreshVaultState();
   }, []);
 
-  useEffect(() => {
-    const unlistenProgress = listen<ProgressPayload>("PROGRESS_EVENT", (event) => {
-      // isCancelling 상태가 true이면, 모든 진행률 이벤트를 무시
-      if (isCancelling) return;
-
-      const {
-        status,
-        current_file_path,
-        number_of_files,
-        current_file_number,
-        total_progress,
-        total_files,
-        suceeded_files,
-        failed_files,
-      } = event.payload;
-      if (status === Status.DONE) {
-        setProgress((prev) => ({
-          ...prev,
-          totalProgress: 100,
-        }));
-        setFileStatus({
-          totalFiles: total_files,
-          suceededFiles: suceeded_files,
-          failedFiles: failed_files,
-        });
-
-        // 100% 진행도를 잠시 보여준 후 완료 화면 표시해 주기
-        setTimeout(() => {
-          setProgress((prev) => ({
-            ...prev,
-            status: Status.DONE,
-            totalProgress: 100,
-          }));
-        }, 1500);
-      } else {
-        setProgress({
-          isVisible: true,
-          status: Status.PROCESSING,
-          currentFile: current_file_path,
-          numberOfFiles: number_of_files,
-          currentFileNumber: current_file_number,
-          totalProgress: total_progress * 100,
-        });
-      }
-    });
-
-    return () => {
-      unlistenProgress.then((f) => f());
-    };
-  }, [isCancelling]);
-
   // --- 화면 렌더링 로직
   if (vaultState === "checking") {
     return <div>{t("message.checking")}</div>;
   }
   if (vaultState === "needs_setup") {
-    return <Setup onSetupComplete={handleSetupSuccess} />;
+    return (
+      <Setup
+        key={vaultVersion}
+        onSetupComplete={handleSetupSuccess}
+        newVaultPath={newVaultPath}
+        onCancelNew={newVaultPath || restoreSource ? refreshVaultState : undefined}
+        onVaultChanged={refreshVaultState}
+        onCreateNew={handleCreateNewVault}
+        restoreSource={restoreSource}
+        onRestore={handleRestoreVault}
+      />
+    );
   }
   if (vaultState === "locked") {
-    return <Unlock onUnlock={handleUnlock} />;
+    return (
+      <Unlock
+        key={vaultVersion}
+        onUnlock={handleUnlock}
+        onVaultChanged={refreshVaultState}
+        onCreateNew={handleCreateNewVault}
+        onRestore={handleRestoreVault}
+      />
+    );
   }
 
   return (
@@ -349,19 +673,37 @@ const App: React.FC = () => {
           numberOfFiles={progress.numberOfFiles}
           currentFileNumber={progress.currentFileNumber}
           totalProgress={progress.totalProgress}
+          processedBytes={progress.processedBytes}
+          totalBytes={progress.totalBytes}
+          bytesPerSecond={progress.bytesPerSecond}
+          etaSeconds={progress.etaSeconds}
           totalFiles={fileStatus.totalFiles}
           suceededFiles={fileStatus.suceededFiles}
           failedFiles={fileStatus.failedFiles}
+          cancelled={fileStatus.cancelled}
+          rolledBack={fileStatus.rolledBack}
+          isCancelling={isCancelling}
           onCancel={handleCancel}
+          onRemoveOutputs={handleRemoveOutputs}
+          onExportReport={fileStatus.jobId ? handleExportReport : undefined}
           onClose={handleCloseProgress}
         />
       )}
+      {pendingDecrypt && (
+        <PasswordPrompt onSubmit={handlePasswordSubmit} onCancel={() => setPendingDecrypt(null)} />
+      )}
       <Header onNavigate={setActivePage} onLogout={handleLock} />
       <div className="app-body">
         <Sidebar activePage={activePage} onNavigate={setActivePage} />
         <main className="app-content">
           {activePage === "settings" ? (
-            <Settings />
+            <Settings onRewrapFiles={handleRewrapFiles} />
+          ) : activePage === "audit" ? (
+            <AuditLog />
+          ) : activePage === "keys" ? (
+            <Keys />
+          ) : activePage === "mirror" ? (
+            <Mirror onMirror={handleMirror} />
           ) : (
             <AppEachContent
               type={activePage}
@@ -369,11 +711,15 @@ const App: React.FC = () => {
               onAddFiles={() =>
                 handleAddFiles(
                   activePage === "decrypt"
-                    ? [{ name: "Encrypted Files", extensions: ["enc"] }]
+                    ? [{ name: "Encrypted Files", extensions: ["enc", "age"] }]
                     : undefined
                 )
               }
               onAddFolder={handleAddFolder}
+              transactional={transactional}
+              onTransactionalChange={setTransactional}
+              encryptOptions={encryptOptions}
+              onEncryptOptionsChange={setEncryptOptions}
               onRemoveFile={handleRemoveFile}
               onClearAll={handleClearAllFiles}
               onButtonClick={onButtonClickByType}
@@ -1,6 +1,7 @@
 import React from "react";
 import { useTranslation } from "react-i18next";
-import { Page, StagedFile } from "../types";
+import { EncryptOptions, Page, StagedFile } from "../types";
+import EncryptOptionsPanel from "./EncryptOptionsPanel";
 import "./AppEachContent.css";
 
 interface AppEachContentProps {
@@ -12,6 +13,10 @@ interface AppEachContentProps {
   onClearAll: () => void;
   onButtonClick: () => void;
   disabled: boolean;
+  transactional: boolean;
+  onTransactionalChange: (value: boolean) => void;
+  encryptOptions: EncryptOptions;
+  onEncryptOptionsChange: (options: EncryptOptions) => void;
 }
 
 const AppEachContent: React.FC<AppEachContentProps> = ({
@@ -23,6 +28,10 @@ const AppEachContent: React.FC<AppEachContentProps> = ({
   onClearAll,
   onButtonClick,
   disabled,
+  transactional,
+  onTransactionalChange,
+  encryptOptions,
+  onEncryptOptionsChange,
 }) => {
   const { t } = useTranslation();
 
@@ -68,6 +77,21 @@ const AppEachContent: React.FC<AppEachContentProps> = ({
         </div>
       )}
 
+      {/* 암호화/복호화는 모두 성공했을 때만 결과를 저장하도록 선택 가능 */}
+      {(type === "encrypt" || type === "decrypt") && (
+        <label className="transaction-option">
+          <input
+            type="checkbox"
+            checked={transactional}
+            onChange={(e) => onTransactionalChange(e.target.checked)}
+          />
+          {t("fileSelector.transactional")}
+        </label>
+      )}
+
+      {/* 암호화할 때 형식(SecureVault/age)과 vault 키 대신 동료의 공개키나 비밀번호로 암호화하도록 선택 가능 */}
+      {type === "encrypt" && <EncryptOptionsPanel options={encryptOptions} onChange={onEncryptOptionsChange} />}
+
       <div className="buttons">
         <button className="button-primary" onClick={onButtonClick} disabled={disabled}>
           {t(`${type}.button`)}
@@ -0,0 +1,132 @@
+import React, { useEffect, useState } from "react";
+import { useTranslation } from "react-i18next";
+import { invoke } from "@tauri-apps/api/core";
+import "./AuditLog.css";
+
+// 감사 로그 항목 (백엔드 audit::AuditEntry)
+type AuditEvent =
+  | { type: "VAULT_CREATED" }
+  | { type: "UNLOCK_SUCCEEDED" }
+  | { type: "UNLOCK_FAILED"; attemptedAt: string }
+  | { type: "PASSWORD_CHANGED" }
+  | { type: "SETTINGS_CHANGED" }
+  | { type: "HEADER_EXPORTED"; rewrapped: boolean }
+  | { type: "HEADER_IMPORTED" }
+  | { type: "IDENTITY_CREATED" | "IDENTITY_DELETED"; publicKey: string }
+  | { type: "CONTACT_ADDED" | "CONTACT_REMOVED"; name: string; publicKey: string }
+  | { type: "KEY_ROTATED"; keyId: string; previousKeyId: string; logProblem: string | null }
+  | { type: "KEYS_RETIRED"; keyIds: string[] }
+  | {
+      type: "JOB";
+      jobId: string;
+      operation: string;
+      status: string;
+      total: number;
+      succeeded: number;
+      failed: number;
+      notStarted: number;
+    }
+  | { type: "LOG_REPAIRED"; discardedBytes: number }
+  | { type: "LOG_TAMPERED"; problem: string };
+
+interface AuditEntry {
+  seq: number;
+  time: string;
+  prevHash: string;
+  event: AuditEvent;
+}
+
+interface AuditLogView {
+  entries: AuditEntry[];
+  verified: boolean;
+  problem: string | null;
+}
+
+const AuditLog: React.FC = () => {
+  const { t } = useTranslation();
+  const [log, setLog] = useState<AuditLogView | null>(null);
+  const [loadError, setLoadError] = useState<string | null>(null);
+
+  // 로그를 읽을 때마다 해시 체인을 다시 검증함
+  const loadLog = async () => {
+    try {
+      setLog(await invoke<AuditLogView>("read_audit_log"));
+      setLoadError(null);
+    } catch (e) {
+      console.error(String(e));
+      setLoadError(String(e));
+    }
+  };
+
+  useEffect(() => {
+    loadLog();
+  }, []);
+
+  const describe = (event: AuditEvent) => {
+    switch (event.type) {
+      case "UNLOCK_FAILED":
+        return t("audit.events.UNLOCK_FAILED", { time: new Date(event.attemptedAt).toLocaleString() });
+      case "JOB":
+        return t("audit.events.JOB", {
+          operation: t(`audit.operations.${event.operation}`),
+          status: t(`audit.status.${event.status}`),
+          total: event.total,
+          succeeded: event.succeeded,
+          failed: event.failed,
+        });
+      case "IDENTITY_CREATED":
+      case "IDENTITY_DELETED":
+        return t(`audit.events.${event.type}`, { key: event.publicKey });
+      case "CONTACT_ADDED":
+      case "CONTACT_REMOVED":
+        return t(`audit.events.${event.type}`, { name: event.name, key: event.publicKey });
+      case "KEY_ROTATED":
+        return event.logProblem
+          ? t("audit.events.KEY_ROTATED_WITH_PROBLEM", { key: event.keyId, previous: event.previousKeyId, problem: event.logProblem })
+          : t("audit.events.KEY_ROTATED", { key: event.keyId, previous: event.previousKeyId });
+      case "KEYS_RETIRED":
+        return t("audit.events.KEYS_RETIRED", { keys: event.keyIds.join(", ") });
+      case "LOG_REPAIRED":
+        return t("audit.events.LOG_REPAIRED", { count: event.discardedBytes });
+      case "LOG_TAMPERED":
+        return t("audit.events.LOG_TAMPERED", { problem: event.problem });
+      default:
+        return t(`audit.events.${event.type}`);
+    }
+  };
+
+  return (
+    <div className="audit-container">
+      <div className="audit-card">
+        <h1 className="audit-title">{t("audit.title")}</h1>
+        <p className="audit-subtitle">{t("audit.subtitle")}</p>
+
+        {loadError && <p className="audit-status tampered">{loadError}</p>}
+        {log && (
+          <p className={`audit-status ${log.verified ? "verified" : "tampered"}`}>
+            {log.verified ? t("audit.verified") : t("audit.tampered", { problem: log.problem })}
+          </p>
+        )}
+
+        <ul className="audit-list">
+          {log && log.entries.length === 0 && <li className="audit-empty">{t("results.noContents")}</li>}
+          {log?.entries
+            .slice()
+            .reverse()
+            .map((entry) => (
+              <li key={entry.seq} className={`audit-item ${["UNLOCK_FAILED", "LOG_TAMPERED"].includes(entry.event.type) ? "warning" : ""}`}>
+                <span className="audit-time">{new Date(entry.time).toLocaleString()}</span>
+                <span className="audit-event">{describe(entry.event)}</span>
+              </li>
+            ))}
+        </ul>
+
+        <button className="button-primary" onClick={loadLog}>
+          {t("audit.verify")}
+        </button>
+      </div>
+    </div>
+  );
+};
+
+export default AuditLog;
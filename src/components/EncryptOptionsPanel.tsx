@@ -0,0 +1,86 @@
+import React from "react";
+import { useTranslation } from "react-i18next";
+import { CIPHERS, Cipher, EncryptOptions } from "../types";
+import RecipientPicker from "./RecipientPicker";
+
+interface EncryptOptionsPanelProps {
+  options: EncryptOptions;
+  onChange: (options: EncryptOptions) => void;
+}
+
+const MODES: EncryptOptions["mode"][] = ["VAULT", "RECIPIENTS", "PASSWORD"];
+
+/******************* 암호화 형식과 누가 열 수 있는지 선택 ******************/
+// SecureVault 형식: vault 키, 수신자 공개키 또는 일회용 비밀번호 (본문 알고리즘도 고를 수 있음)
+// age 형식: 수신자 공개키 또는 비밀번호 (age 도구로 열 수 있음)
+const EncryptOptionsPanel: React.FC<EncryptOptionsPanelProps> = ({ options, onChange }) => {
+  const { t } = useTranslation();
+  const update = (changes: Partial<EncryptOptions>) => onChange({ ...options, ...changes });
+
+  // age 파일은 vault 키로 암호화할 수 없으므로 비밀번호로 바꿈
+  const handleFormatChange = (format: EncryptOptions["format"]) => {
+    const mode = format === "AGE" && options.mode === "VAULT" ? "PASSWORD" : options.mode;
+    update({ format, mode });
+  };
+
+  return (
+    <div className="encrypt-options">
+      <label className="transaction-option">
+        {t("encryptOptions.format")}
+        <select value={options.format} onChange={(e) => handleFormatChange(e.target.value as EncryptOptions["format"])}>
+          <option value="SECURE_VAULT">{t("encryptOptions.secureVault")}</option>
+          <option value="AGE">{t("encryptOptions.age")}</option>
+        </select>
+      </label>
+      {options.format === "SECURE_VAULT" && (
+        <label className="transaction-option">
+          {t("encryptOptions.cipher")}
+          <select
+            value={options.cipher ?? ""}
+            onChange={(e) => update({ cipher: (e.target.value || null) as Cipher | null })}
+          >
+            <option value="">{t("encryptOptions.defaultCipher")}</option>
+            {CIPHERS.map((cipher) => (
+              <option key={cipher} value={cipher}>
+                {t(`ciphers.${cipher}`)}
+              </option>
+            ))}
+          </select>
+        </label>
+      )}
+      {MODES.map((mode) => (
+        <label key={mode} className="transaction-option">
+          <input
+            type="radio"
+            name="encrypt-mode"
+            checked={options.mode === mode}
+            disabled={mode === "VAULT" && options.format === "AGE"}
+            onChange={() => update({ mode })}
+          />
+          {t(`encryptOptions.modes.${mode}`)}
+        </label>
+      ))}
+      {options.mode === "RECIPIENTS" && (
+        <RecipientPicker selected={options.recipients} onSelectedChange={(recipients) => update({ recipients })} />
+      )}
+      {options.mode === "PASSWORD" && (
+        <div className="encrypt-password">
+          <input
+            type="password"
+            placeholder={t("encryptOptions.password")}
+            value={options.password}
+            onChange={(e) => update({ password: e.target.value })}
+          />
+          <input
+            type="password"
+            placeholder={t("encryptOptions.confirmPassword")}
+            value={options.confirm}
+            onChange={(e) => update({ confirm: e.target.value })}
+          />
+        </div>
+      )}
+    </div>
+  );
+};
+
+export default EncryptOptionsPanel;
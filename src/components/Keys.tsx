@@ -0,0 +1,169 @@
+import React, { useEffect, useState } from "react";
+import { useTranslation } from "react-i18next";
+import { invoke } from "@tauri-apps/api/core";
+import { ask, message } from "@tauri-apps/plugin-dialog";
+import "./Keys.css";
+
+// 이 vault 의 X25519 키 쌍 (백엔드 keybook::IdentityInfo, 비밀키는 보내지 않음)
+export interface IdentityInfo {
+  id: string;
+  name: string;
+  publicKey: string;
+  createdAt: string;
+}
+
+// 주소록에 등록한 동료의 공개키 (백엔드 keybook::Contact)
+export interface Contact {
+  id: string;
+  name: string;
+  publicKey: string;
+  addedAt: string;
+}
+
+const Keys: React.FC = () => {
+  const { t } = useTranslation();
+  const [identities, setIdentities] = useState<IdentityInfo[]>([]);
+  const [contacts, setContacts] = useState<Contact[]>([]);
+  const [identityName, setIdentityName] = useState("");
+  const [contactName, setContactName] = useState("");
+  const [contactKey, setContactKey] = useState("");
+
+  const loadKeys = async () => {
+    try {
+      setIdentities(await invoke<IdentityInfo[]>("list_identities"));
+      setContacts(await invoke<Contact[]>("list_contacts"));
+    } catch (e) {
+      console.error(String(e));
+      await message(String(e));
+    }
+  };
+
+  useEffect(() => {
+    loadKeys();
+  }, []);
+
+  const runAndReload = async (command: string, args: Record<string, unknown>) => {
+    try {
+      await invoke(command, args);
+      await loadKeys();
+      return true;
+    } catch (e) {
+      console.error(String(e));
+      await message(String(e));
+      return false;
+    }
+  };
+
+  const handleCreateIdentity = async () => {
+    const name = identityName.trim() || t("keys.defaultIdentityName");
+    if (await runAndReload("create_identity", { name })) {
+      setIdentityName("");
+    }
+  };
+
+  // 이 키 쌍으로만 열 수 있는 파일은 더 이상 복호화할 수 없으므로 확인 후 삭제
+  const handleDeleteIdentity = async (identity: IdentityInfo) => {
+    const confirmed = await ask(t("keys.deleteIdentityConfirm", { name: identity.name }), { kind: "warning" });
+    if (confirmed) {
+      await runAndReload("delete_identity", { id: identity.id });
+    }
+  };
+
+  const handleAddContact = async () => {
+    if (!contactName.trim() || !contactKey.trim()) {
+      await message(t("error.allFieldsRequired"));
+      return;
+    }
+    if (await runAndReload("add_contact", { name: contactName, publicKey: contactKey })) {
+      setContactName("");
+      setContactKey("");
+    }
+  };
+
+  const handleRemoveContact = (contact: Contact) => runAndReload("remove_contact", { id: contact.id });
+
+  const handleCopy = async (publicKey: string) => {
+    await navigator.clipboard.writeText(publicKey);
+    await message(t("keys.copied"));
+  };
+
+  return (
+    <div className="keys-container">
+      <div className="keys-card">
+        <h1 className="keys-title">{t("keys.title")}</h1>
+        <p className="keys-subtitle">{t("keys.subtitle")}</p>
+
+        <h2 className="keys-section">{t("keys.identities")}</h2>
+        <p className="keys-description">{t("keys.identitiesDesc")}</p>
+        {identities.length === 0 ? (
+          <p className="keys-empty">{t("keys.noIdentities")}</p>
+        ) : (
+          <ul className="keys-list">
+            {identities.map((identity) => (
+              <li key={identity.id} className="keys-item">
+                <div className="keys-item-text">
+                  <span className="keys-item-name">{identity.name}</span>
+                  <code className="keys-item-key">{identity.publicKey}</code>
+                </div>
+                <button onClick={() => handleCopy(identity.publicKey)}>{t("keys.copy")}</button>
+                <button className="keys-danger" onClick={() => handleDeleteIdentity(identity)}>
+                  {t("keys.delete")}
+                </button>
+              </li>
+            ))}
+          </ul>
+        )}
+        <div className="keys-form">
+          <input
+            className="keys-input"
+            placeholder={t("keys.identityName")}
+            value={identityName}
+            onChange={(e) => setIdentityName(e.target.value)}
+          />
+          <button className="button-primary" onClick={handleCreateIdentity}>
+            {t("keys.createIdentity")}
+          </button>
+        </div>
+
+        <h2 className="keys-section">{t("keys.contacts")}</h2>
+        <p className="keys-description">{t("keys.contactsDesc")}</p>
+        {contacts.length === 0 ? (
+          <p className="keys-empty">{t("keys.noContacts")}</p>
+        ) : (
+          <ul className="keys-list">
+            {contacts.map((contact) => (
+              <li key={contact.id} className="keys-item">
+                <div className="keys-item-text">
+                  <span className="keys-item-name">{contact.name}</span>
+                  <code className="keys-item-key">{contact.publicKey}</code>
+                </div>
+                <button className="keys-danger" onClick={() => handleRemoveContact(contact)}>
+                  {t("keys.remove")}
+                </button>
+              </li>
+            ))}
+          </ul>
+        )}
+        <div className="keys-form">
+          <input
+            className="keys-input keys-input-name"
+            placeholder={t("keys.contactName")}
+            value={contactName}
+            onChange={(e) => setContactName(e.target.value)}
+          />
+          <input
+            className="keys-input"
+            placeholder="age1..."
+            value={contactKey}
+            onChange={(e) => setContactKey(e.target.value)}
+          />
+          <button className="button-primary" onClick={handleAddContact}>
+            {t("keys.addContact")}
+          </button>
+        </div>
+      </div>
+    </div>
+  );
+};
+
+export default Keys;
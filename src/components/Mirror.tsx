@@ -0,0 +1,53 @@
+import React, { useState } from "react";
+import { useTranslation } from "react-i18next";
+import { open } from "@tauri-apps/plugin-dialog";
+import "./Mirror.css";
+
+interface MirrorProps {
+  // 폴더 미러 작업 (진행 상황은 App 의 진행 창에 표시)
+  onMirror: (sourceDir: string, destinationDir: string) => Promise<void>;
+}
+
+const Mirror: React.FC<MirrorProps> = ({ onMirror }) => {
+  const { t } = useTranslation();
+  const [sourceDir, setSourceDir] = useState("");
+  const [destinationDir, setDestinationDir] = useState("");
+
+  const chooseFolder = async (title: string, onChosen: (folder: string) => void) => {
+    const selected = await open({ title, directory: true });
+    if (typeof selected === "string") onChosen(selected);
+  };
+
+  return (
+    <div className="mirror-container">
+      <div className="mirror-card">
+        <h1 className="mirror-title">{t("mirror.title")}</h1>
+        <p className="mirror-subtitle">{t("mirror.subtitle")}</p>
+
+        <h2 className="mirror-section">{t("mirror.source")}</h2>
+        <p className="mirror-description">{t("mirror.sourceDesc")}</p>
+        <div className="mirror-folder">
+          <code className="mirror-path">{sourceDir || t("mirror.notSelected")}</code>
+          <button onClick={() => chooseFolder(t("mirror.source"), setSourceDir)}>{t("mirror.choose")}</button>
+        </div>
+
+        <h2 className="mirror-section">{t("mirror.destination")}</h2>
+        <p className="mirror-description">{t("mirror.destinationDesc")}</p>
+        <div className="mirror-folder">
+          <code className="mirror-path">{destinationDir || t("mirror.notSelected")}</code>
+          <button onClick={() => chooseFolder(t("mirror.destination"), setDestinationDir)}>{t("mirror.choose")}</button>
+        </div>
+
+        <button
+          className="button-primary mirror-start"
+          disabled={!sourceDir || !destinationDir}
+          onClick={() => onMirror(sourceDir, destinationDir)}
+        >
+          {t("mirror.start")}
+        </button>
+      </div>
+    </div>
+  );
+};
+
+export default Mirror;
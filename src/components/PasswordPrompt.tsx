@@ -0,0 +1,45 @@
+import React, { useState } from "react";
+import { useTranslation } from "react-i18next";
+import "./PasswordPrompt.css";
+
+interface PasswordPromptProps {
+  onSubmit: (password: string) => void;
+  onCancel: () => void;
+}
+
+/******************* 비밀번호로 암호화된 파일을 복호화할 때 비밀번호 입력 ******************/
+const PasswordPrompt: React.FC<PasswordPromptProps> = ({ onSubmit, onCancel }) => {
+  const { t } = useTranslation();
+  const [password, setPassword] = useState("");
+
+  const handleSubmit = () => {
+    if (password) onSubmit(password);
+  };
+
+  return (
+    <div className="password-prompt-overlay">
+      <div className="password-prompt-box">
+        <h2>{t("passwordPrompt.title")}</h2>
+        <p>{t("passwordPrompt.description")}</p>
+        <input
+          type="password"
+          className="password-prompt-input"
+          autoFocus
+          value={password}
+          onChange={(e) => setPassword(e.target.value)}
+          onKeyDown={(e) => e.key === "Enter" && handleSubmit()}
+        />
+        <div className="password-prompt-buttons">
+          <button className="password-prompt-cancel" onClick={onCancel}>
+            {t("passwordPrompt.cancel")}
+          </button>
+          <button className="button-primary" onClick={handleSubmit} disabled={!password}>
+            {t("passwordPrompt.submit")}
+          </button>
+        </div>
+      </div>
+    </div>
+  );
+};
+
+export default PasswordPrompt;
@@ -2,17 +2,26 @@ import React, { useEffect } from "react";
 import { useTranslation } from "react-i18next";
 import "./ProgressDialog.css";
 import ShowResultView from "./ShowResultView";
+import type { CancelledJob, RolledBackJob } from "../App";
 
 export enum Status {
   IDLE = "IDLE",
   PROCESSING = "PROCESSING",
   DONE = "DONE",
+  CANCELLED = "CANCELLED",
   ERROR = "ERROR",
 }
 
 export type EachFile = {
   path: string;
   error: string;
+  scheme?: string; // 보안 삭제 시 사용한 덮어쓰기 방식
+  note?: string; // 링크/특수 파일 등 항목별 처리 내용
+  confidence?: "HIGH" | "LOW" | "NONE" | "UNKNOWN"; // 보안 삭제 결과를 신뢰할 수 있는 정도
+  output?: string;
+  error_code?: string; // 보고서용 오류 분류
+  size?: number;
+  duration_ms?: number;
 };
 
 interface ProgressDialogProps {
@@ -21,10 +30,19 @@ interface ProgressDialogProps {
   numberOfFiles: number;
   currentFileNumber: number;
   totalProgress: number;
+  processedBytes: number;
+  totalBytes: number;
+  bytesPerSecond: number;
+  etaSeconds?: number | null; // 처리 속도를 아직 알 수 없으면 null
   totalFiles: string[];
   suceededFiles: EachFile[];
   failedFiles: EachFile[] | undefined;
+  cancelled?: CancelledJob;
+  rolledBack?: RolledBackJob;
+  isCancelling: boolean;
   onCancel: () => void;
+  onRemoveOutputs: () => void;
+  onExportReport?: () => void;
   onClose: () => void;
 }
 
@@ -39,16 +57,46 @@ const getFileName = (path: string): string => {
   return path.replace(/^.*[\\\/]/, "");
 };
 
+// 바이트 수를 읽기 쉬운 단위로 변환
+const formatBytes = (bytes: number): string => {
+  const units = ["B", "KB", "MB", "GB", "TB"];
+  let value = bytes;
+  let unitIndex = 0;
+  while (value >= 1024 && unitIndex < units.length - 1) {
+    value /= 1024;
+    unitIndex++;
+  }
+  return `${value.toFixed(unitIndex === 0 ? 0 : 1)} ${units[unitIndex]}`;
+};
+
+// 남은 시간을 "1:05:09" / "5:09" 형태로 변환
+const formatDuration = (seconds: number): string => {
+  const total = Math.ceil(seconds);
+  const h = Math.floor(total / 3600);
+  const m = Math.floor((total % 3600) / 60);
+  const s = String(total % 60).padStart(2, "0");
+  return h > 0 ? `${h}:${String(m).padStart(2, "0")}:${s}` : `${m}:${s}`;
+};
+
 const ProgressDialog: React.FC<ProgressDialogProps> = ({
   status,
   currentFile,
   numberOfFiles,
   currentFileNumber,
   totalProgress,
+  processedBytes,
+  totalBytes,
+  bytesPerSecond,
+  etaSeconds,
   totalFiles,
   suceededFiles,
   failedFiles,
+  cancelled,
+  rolledBack,
+  isCancelling,
   onCancel,
+  onRemoveOutputs,
+  onExportReport,
   onClose,
 }) => {
   const { t } = useTranslation();
@@ -81,9 +129,17 @@ const ProgressDialog: React.FC<ProgressDialogProps> = ({
             <div className="progress-bar" style={{ width: `${totalProgress}%` }}></div>
           </div>
           <p className="progress-percentage">{Math.round(totalProgress)}%</p>
+          {totalBytes > 0 && (
+            <p className="progress-detail">
+              {formatBytes(processedBytes)} / {formatBytes(totalBytes)}
+              {bytesPerSecond > 0 && ` · ${formatBytes(bytesPerSecond)}/s`}
+              {etaSeconds != null &&
+                ` · ${t("progress.remaining", { time: formatDuration(etaSeconds) })}`}
+            </p>
+          )}
 
-          <button className="dialog-button cancel" onClick={onCancel}>
-            {t("progress.cancel")}
+          <button className="dialog-button cancel" onClick={onCancel} disabled={isCancelling}>
+            {isCancelling ? t("progress.cancelling") : t("progress.cancel")}
           </button>
         </div>
       ) : (
@@ -92,6 +148,10 @@ const ProgressDialog: React.FC<ProgressDialogProps> = ({
             totalFiles={totalFiles}
             suceededFiles={suceededFiles}
             failedFiles={failedFiles}
+            cancelled={cancelled}
+            rolledBack={rolledBack}
+            onRemoveOutputs={onRemoveOutputs}
+            onExportReport={onExportReport}
             onRetry={() => {}}
             onClose={onClose}
           />
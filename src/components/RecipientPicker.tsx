@@ -0,0 +1,56 @@
+import React, { useEffect, useState } from "react";
+import { useTranslation } from "react-i18next";
+import { invoke } from "@tauri-apps/api/core";
+import { Contact, IdentityInfo } from "./Keys";
+import "./RecipientPicker.css";
+
+interface RecipientPickerProps {
+  selected: string[]; // 선택한 수신자 공개키
+  onSelectedChange: (value: string[]) => void;
+}
+
+/******************* 암호화할 때 파일을 열 수 있는 수신자 선택 ******************/
+// 내 키 쌍도 수신자로 고를 수 있으며, 고르지 않으면 내가 암호화한 파일이라도 열 수 없음
+const RecipientPicker: React.FC<RecipientPickerProps> = ({ selected, onSelectedChange }) => {
+  const { t } = useTranslation();
+  const [identities, setIdentities] = useState<IdentityInfo[]>([]);
+  const [contacts, setContacts] = useState<Contact[]>([]);
+
+  // 처음 열 때는 내 키 쌍을 미리 선택해 둠
+  useEffect(() => {
+    invoke<IdentityInfo[]>("list_identities")
+      .then((loaded) => {
+        setIdentities(loaded);
+        if (selected.length === 0) {
+          onSelectedChange(loaded.map((identity) => identity.publicKey));
+        }
+      })
+      .catch(console.error);
+    invoke<Contact[]>("list_contacts").then(setContacts).catch(console.error);
+  }, []);
+
+  const toggle = (publicKey: string) => {
+    onSelectedChange(
+      selected.includes(publicKey) ? selected.filter((key) => key !== publicKey) : [...selected, publicKey]
+    );
+  };
+
+  const renderOption = (id: string, name: string, publicKey: string) => (
+    <label key={id} className="recipient-option" title={publicKey}>
+      <input type="checkbox" checked={selected.includes(publicKey)} onChange={() => toggle(publicKey)} />
+      {name}
+    </label>
+  );
+
+  return (
+    <div className="recipient-list">
+      {identities.length === 0 && contacts.length === 0 && <p>{t("recipients.empty")}</p>}
+      {identities.map((identity) =>
+        renderOption(identity.id, t("recipients.mine", { name: identity.name }), identity.publicKey)
+      )}
+      {contacts.map((contact) => renderOption(contact.id, contact.name, contact.publicKey))}
+    </div>
+  );
+};
+
+export default RecipientPicker;
@@ -1,8 +1,9 @@
 import React, { useState, useEffect } from "react";
 import { useTranslation } from "react-i18next";
 import { invoke } from "@tauri-apps/api/core";
-import { message } from "@tauri-apps/plugin-dialog";
+import { ask, message, open, save } from "@tauri-apps/plugin-dialog";
 import zxcvbn from "zxcvbn";
+import { CIPHERS, Cipher } from "../types";
 import "./Settings.css";
 
 // 비밀번호 강도를 시각적으로 표시할 컴포넌트
@@ -33,8 +34,124 @@ const PasswordStrengthMeter: React.FC<{ score: number }> = ({ score }) => {
   );
 };
 
-const Settings: React.FC = () => {
+// 잠금 해제 실패 시 대기 정책 (백엔드 throttle::UnlockPolicy)
+interface UnlockPolicy {
+  freeAttempts: number;
+  baseDelaySeconds: number;
+  maxDelaySeconds: number;
+  wipeAfter: number | null;
+}
+
+// 앱 설정 (백엔드 settings::AppSettings)
+interface AppSettings {
+  unlockPolicy: UnlockPolicy;
+  defaultCipher: Cipher;
+}
+
+// 마스터 키 교체 진행 상황 (백엔드 rotation::RotationStatus)
+interface RotationStatus {
+  currentKeyId: string;
+  previousKeyIds: string[]; // 비어 있지 않으면 아직 파일을 옮기는 중
+  folders: string[];
+  rotatedAt: string | null;
+}
+
+interface SettingsProps {
+  onRewrapFiles: () => Promise<void>; // 파일을 새 키로 옮기는 작업 (진행 상황은 App 의 진행 창에 표시)
+}
+
+const Settings: React.FC<SettingsProps> = ({ onRewrapFiles }) => {
   const { t } = useTranslation();
+  const [settings, setSettings] = useState<AppSettings | null>(null);
+  const [rotation, setRotation] = useState<RotationStatus | null>(null);
+
+  const loadRotation = () => invoke<RotationStatus>("get_rotation_status").then(setRotation).catch(console.error);
+
+  useEffect(() => {
+    invoke<AppSettings>("get_settings").then(setSettings).catch(console.error);
+    loadRotation();
+  }, []);
+
+  const updatePolicy = (changes: Partial<UnlockPolicy>) => {
+    setSettings((prev) => prev && { ...prev, unlockPolicy: { ...prev.unlockPolicy, ...changes } });
+  };
+
+  const [exportPassword, setExportPassword] = useState("");
+  const [exportConfirm, setExportConfirm] = useState("");
+
+  // vault 헤더를 다른 위치에 보관. 내보내기 비밀번호를 비워 두면 현재 비밀번호로 암호화된 그대로 저장
+  const handleExportHeader = async () => {
+    if (exportPassword && exportPassword.length < 8) {
+      await message(t("error.passwordTooShort"));
+      return;
+    }
+    if (exportPassword !== exportConfirm) {
+      await message(t("error.passwordsNoMatch"));
+      return;
+    }
+    const path = await save({ title: t("settings.exportHeader"), defaultPath: "securevault-header.key" });
+    if (!path) return;
+    try {
+      await invoke("export_vault_header", { path, exportPassword: exportPassword || undefined });
+      setExportPassword("");
+      setExportConfirm("");
+      await message(t("messages.headerExported"));
+    } catch (e) {
+      console.error(String(e));
+      await message(String(e));
+    }
+  };
+
+  const [rotationFolders, setRotationFolders] = useState<string[]>([]);
+  const [rotationPassword, setRotationPassword] = useState("");
+
+  const handleAddRotationFolder = async () => {
+    const selected = await open({ title: t("settings.rotationAddFolder"), directory: true, multiple: true });
+    if (!selected) return;
+    const folders = Array.isArray(selected) ? selected : [selected];
+    setRotationFolders((prev) => [...prev, ...folders.filter((folder) => !prev.includes(folder))]);
+  };
+
+  // 이전 키로 암호화된 파일을 새 키로 옮기고, 모두 옮겨졌으면 이전 키가 폐기됨
+  const handleRewrap = async () => {
+    await onRewrapFiles();
+    await loadRotation();
+  };
+
+  // 새 마스터 키로 바꾼 뒤 바로 선택한 폴더의 파일을 옮기기 시작
+  const handleRotateKey = async () => {
+    if (!rotationPassword) {
+      await message(t("error.allFieldsRequired"));
+      return;
+    }
+    const confirmed = await ask(t("settings.rotationConfirm"), {
+      title: t("settings.rotation"),
+      okLabel: t("settings.rotate"),
+      cancelLabel: t("progress.cancel"),
+    });
+    if (!confirmed) return;
+    try {
+      await invoke("rotate_master_key", { password: rotationPassword, folders: rotationFolders });
+      setRotationPassword("");
+      setRotationFolders([]);
+    } catch (e) {
+      console.error(String(e));
+      await message(String(e));
+      return;
+    }
+    await handleRewrap();
+  };
+
+  const handleSaveSettings = async () => {
+    if (!settings) return;
+    try {
+      await invoke("save_settings", { settings });
+      await message(t("messages.settingsSaved"));
+    } catch (e) {
+      console.error(String(e));
+      await message(String(e));
+    }
+  };
 
   const [currentPassword, setCurrentPassword] = useState("");
   const [newPassword, setNewPassword] = useState("");
@@ -130,6 +247,185 @@ const Settings: React.FC = () => {
           {t("settings.button")}
         </button>
       </div>
+
+      <div className="settings-card">
+        <p className="settings-subtitle">{t("settings.headerBackup")}</p>
+        <p className="settings-description">{t("settings.headerBackupDesc")}</p>
+
+        <div className="input-group">
+          <label className="input-label">{t("settings.exportPassword")}</label>
+          <input
+            type="password"
+            className="input-field"
+            value={exportPassword}
+            onChange={(e) => setExportPassword(e.target.value)}
+          />
+        </div>
+        {exportPassword && (
+          <div className="input-group">
+            <label className="input-label">{t("settings.confirmPassword")}</label>
+            <input
+              type="password"
+              className="input-field"
+              value={exportConfirm}
+              onChange={(e) => setExportConfirm(e.target.value)}
+            />
+          </div>
+        )}
+
+        <button className="button-primary" onClick={handleExportHeader}>
+          {t("settings.exportHeader")}
+        </button>
+      </div>
+
+      {settings && (
+        <div className="settings-card">
+          <p className="settings-subtitle">{t("settings.unlockPolicy")}</p>
+          <p className="settings-description">{t("settings.unlockPolicyDesc")}</p>
+
+          <div className="input-group">
+            <label className="input-label">{t("settings.freeAttempts")}</label>
+            <input
+              type="number"
+              min={0}
+              className="input-field"
+              value={settings.unlockPolicy.freeAttempts}
+              onChange={(e) => updatePolicy({ freeAttempts: Number(e.target.value) })}
+            />
+          </div>
+
+          <div className="input-group">
+            <label className="input-label">{t("settings.baseDelay")}</label>
+            <input
+              type="number"
+              min={0}
+              className="input-field"
+              value={settings.unlockPolicy.baseDelaySeconds}
+              onChange={(e) => updatePolicy({ baseDelaySeconds: Number(e.target.value) })}
+            />
+          </div>
+
+          <div className="input-group">
+            <label className="input-label">{t("settings.maxDelay")}</label>
+            <input
+              type="number"
+              min={0}
+              className="input-field"
+              value={settings.unlockPolicy.maxDelaySeconds}
+              onChange={(e) => updatePolicy({ maxDelaySeconds: Number(e.target.value) })}
+            />
+          </div>
+
+          <div className="input-group">
+            <label className="input-label">
+              <input
+                type="checkbox"
+                checked={settings.unlockPolicy.wipeAfter != null}
+                onChange={(e) => updatePolicy({ wipeAfter: e.target.checked ? 10 : null })}
+              />{" "}
+              {t("settings.wipeEnabled")}
+            </label>
+            {settings.unlockPolicy.wipeAfter != null && (
+              <>
+                <label className="input-label">{t("settings.wipeAfter")}</label>
+                <input
+                  type="number"
+                  min={1}
+                  className="input-field"
+                  value={settings.unlockPolicy.wipeAfter}
+                  onChange={(e) => updatePolicy({ wipeAfter: Math.max(1, Number(e.target.value)) })}
+                />
+                <p className="message error">{t("settings.wipeWarning")}</p>
+              </>
+            )}
+          </div>
+
+          <button className="button-primary" onClick={handleSaveSettings}>
+            {t("settings.savePolicy")}
+          </button>
+        </div>
+      )}
+
+      {settings && (
+        <div className="settings-card">
+          <p className="settings-subtitle">{t("settings.encryption")}</p>
+          <p className="settings-description">{t("settings.encryptionDesc")}</p>
+
+          <div className="input-group">
+            <label className="input-label">{t("settings.defaultCipher")}</label>
+            <select
+              className="input-field"
+              value={settings.defaultCipher}
+              onChange={(e) => setSettings({ ...settings, defaultCipher: e.target.value as Cipher })}
+            >
+              {CIPHERS.map((cipher) => (
+                <option key={cipher} value={cipher}>
+                  {t(`ciphers.${cipher}`)}
+                </option>
+              ))}
+            </select>
+          </div>
+
+          <button className="button-primary" onClick={handleSaveSettings}>
+            {t("settings.saveEncryption")}
+          </button>
+        </div>
+      )}
+
+      {rotation && (
+        <div className="settings-card">
+          <p className="settings-subtitle">{t("settings.rotation")}</p>
+          <p className="settings-description">{t("settings.rotationDesc")}</p>
+          <p className="settings-description">{t("settings.currentKeyId", { id: rotation.currentKeyId })}</p>
+
+          {rotation.previousKeyIds.length > 0 ? (
+            <>
+              <p className="message error">
+                {t("settings.rotationPending", {
+                  ids: rotation.previousKeyIds.join(", "),
+                  time: rotation.rotatedAt ? new Date(rotation.rotatedAt).toLocaleString() : "",
+                })}
+              </p>
+              <ul className="rotation-folders">
+                {rotation.folders.map((folder) => (
+                  <li key={folder}>{folder}</li>
+                ))}
+              </ul>
+              <button className="button-primary" onClick={handleRewrap}>
+                {t("settings.rotationResume")}
+              </button>
+            </>
+          ) : (
+            <>
+              <ul className="rotation-folders">
+                {rotationFolders.map((folder) => (
+                  <li key={folder}>
+                    {folder}
+                    <button onClick={() => setRotationFolders((prev) => prev.filter((f) => f !== folder))}>×</button>
+                  </li>
+                ))}
+              </ul>
+              <button className="rotation-add-folder" onClick={handleAddRotationFolder}>
+                {t("settings.rotationAddFolder")}
+              </button>
+
+              <div className="input-group">
+                <label className="input-label">{t("settings.currentPassword")}</label>
+                <input
+                  type="password"
+                  className="input-field"
+                  value={rotationPassword}
+                  onChange={(e) => setRotationPassword(e.target.value)}
+                />
+              </div>
+
+              <button className="button-primary" onClick={handleRotateKey}>
+                {t("settings.rotate")}
+              </button>
+            </>
+          )}
+        </div>
+      )}
     </div>
   );
 };
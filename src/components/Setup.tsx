@@ -2,18 +2,65 @@ import React, { useState } from "react";
 import { invoke } from "@tauri-apps/api/core";
 import { useTranslation } from "react-i18next";
 import LanguageSwitcher from "./LanguageSwitcher";
+import VaultSwitcher from "./VaultSwitcher";
 import "./Setup.css";
 
 interface SetupProps {
   onSetupComplete: (password: string) => void;
+  newVaultPath?: string; // 새 vault 를 만들 위치 (없으면 선택된 vault 위치에 만듦)
+  onCancelNew?: () => void;
+  onVaultChanged: () => void;
+  onCreateNew: (path: string) => void;
+  restoreSource?: string; // 복원할 헤더 파일 (있으면 새로 만드는 대신 복원)
+  onRestore: (source: string) => void;
 }
 
-const Setup: React.FC<SetupProps> = ({ onSetupComplete }) => {
+const Setup: React.FC<SetupProps> = ({
+  onSetupComplete,
+  newVaultPath,
+  onCancelNew,
+  onVaultChanged,
+  onCreateNew,
+  restoreSource,
+  onRestore,
+}) => {
   const { t } = useTranslation();
   const [password, setPassword] = useState("");
   const [confirm, setConfirm] = useState("");
   const [errorKey, setErrorKey] = useState<string | null>(null);
 
+  const [backupPassword, setBackupPassword] = useState("");
+
+  // 헤더를 암호화한 비밀번호로 확인한 뒤 복원. 새 비밀번호를 비워 두면 그 비밀번호를 그대로 사용
+  const handleRestoreVault = async () => {
+    if (!backupPassword) {
+      setErrorKey("error.allFieldsRequired");
+      return;
+    }
+    if (password && password.length < 8) {
+      setErrorKey("error.passwordTooShort");
+      return;
+    }
+    if (password !== confirm) {
+      setErrorKey("error.passwordsNoMatch");
+      return;
+    }
+    try {
+      await invoke("import_vault_header", {
+        source: restoreSource,
+        password: backupPassword,
+        newPassword: password || undefined,
+        path: newVaultPath,
+      });
+      onSetupComplete(password || backupPassword);
+      alert(t("messages.vaultRestoredSuccess"));
+      setErrorKey("");
+    } catch (e) {
+      console.error(String(e));
+      setErrorKey("error.restoreFailed");
+    }
+  };
+
   const handleCreateVault = async () => {
     if (!password || !confirm) {
       setErrorKey("error.allFieldsRequired");
@@ -30,7 +77,7 @@ const Setup: React.FC<SetupProps> = ({ onSetupComplete }) => {
       return;
     }
     try {
-      await invoke("create_vault", { password });
+      await invoke("create_vault", { password, path: newVaultPath });
       onSetupComplete(password);
       alert(t("messages.vaultCreatedSuccess"));
       setErrorKey("");
@@ -44,12 +91,35 @@ const Setup: React.FC<SetupProps> = ({ onSetupComplete }) => {
     <div className="setup-container">
       <LanguageSwitcher isAbsolute={true} />
       <div className="setup-box">
-        <h1>{t("setup.title")}</h1>
-        <p>{t("setup.prompt")}</p>
+        <h1>{restoreSource ? t("setup.restoreTitle") : t("setup.title")}</h1>
+        {restoreSource && (
+          <p className="setup-location" title={restoreSource}>
+            {t("vaults.restoreFrom", { path: restoreSource })}
+          </p>
+        )}
+        {newVaultPath ? (
+          <p className="setup-location" title={newVaultPath}>
+            {t("vaults.newLocation", { path: newVaultPath })}
+          </p>
+        ) : (
+          !restoreSource && (
+            <VaultSwitcher onChanged={onVaultChanged} onCreateNew={onCreateNew} onRestore={onRestore} />
+          )
+        )}
+        <p>{restoreSource ? t("setup.restorePrompt") : t("setup.prompt")}</p>
+        {restoreSource && (
+          <input
+            className="setup-input"
+            type="password"
+            placeholder={t("setup.backupPassword")}
+            value={backupPassword}
+            onChange={(e) => setBackupPassword(e.target.value)}
+          />
+        )}
         <input
           className="setup-input"
           type="password"
-          placeholder={t("setup.placeholder1")}
+          placeholder={restoreSource ? t("setup.newPasswordOptional") : t("setup.placeholder1")}
           value={password}
           onChange={(e) => setPassword(e.target.value)}
         />
@@ -65,9 +135,14 @@ const Setup: React.FC<SetupProps> = ({ onSetupComplete }) => {
             {t(errorKey)}
           </p>
         )}
-        <button className="setup-button" onClick={handleCreateVault}>
-          {t("setup.button")}
+        <button className="setup-button" onClick={restoreSource ? handleRestoreVault : handleCreateVault}>
+          {restoreSource ? t("setup.restoreButton") : t("setup.button")}
         </button>
+        {onCancelNew && (
+          <button className="setup-cancel" onClick={onCancelNew}>
+            {t("vaults.cancel")}
+          </button>
+        )}
       </div>
     </div>
   );
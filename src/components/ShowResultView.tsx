@@ -2,11 +2,16 @@ import React, { useState } from "react";
 import { useTranslation } from "react-i18next";
 import "./ShowResultView.css";
 import { EachFile } from "./ProgressDialog";
+import type { CancelledJob, RolledBackJob } from "../App";
 
 interface ShowResultViewProps {
   totalFiles: string[];
   suceededFiles: EachFile[];
   failedFiles: EachFile[] | undefined;
+  cancelled?: CancelledJob;
+  rolledBack?: RolledBackJob;
+  onRemoveOutputs: () => void;
+  onExportReport?: () => void; // 작업 ID 를 받은 경우에만 표시
   onRetry: (failedPaths: string[]) => void;
   onClose: () => void;
 }
@@ -17,10 +22,15 @@ const ShowResultView: React.FC<ShowResultViewProps> = ({
   totalFiles,
   suceededFiles,
   failedFiles = [],
+  cancelled,
+  rolledBack,
+  onRemoveOutputs,
+  onExportReport,
   onClose,
 }) => {
   const { t } = useTranslation();
-  const [activeTab, setActiveTab] = useState<"all" | "success" | "error">("all");
+  const notStarted = [...(cancelled?.notStarted ?? []), ...(rolledBack?.notStarted ?? [])];
+  const [activeTab, setActiveTab] = useState<"all" | "success" | "error" | "notStarted">("all");
 
   const renderFileList = () => {
     switch (activeTab) {
@@ -38,6 +48,12 @@ const ShowResultView: React.FC<ShowResultViewProps> = ({
             <div className="file-name-div">
               <span className="icon">✓</span>
               <span className="file-name">{getFileName(file.path)}</span>
+              {file.note && <span className="error-message">({file.note})</span>}
+              {file.scheme && (
+                <span className="file-scheme">
+                  {file.confidence ? `${file.scheme} · ${file.confidence}` : file.scheme}
+                </span>
+              )}
             </div>
           </div>
         ));
@@ -57,6 +73,14 @@ const ShowResultView: React.FC<ShowResultViewProps> = ({
         } else {
           return <div className="result-noitem">{t("results.noContents")}</div>;
         }
+      case "notStarted":
+        return notStarted.map((filePath, index) => (
+          <div key={`not-started-${index}`} className="result-item not-started">
+            <div className="file-name-div">
+              <span className="file-name">{getFileName(filePath)}</span>
+            </div>
+          </div>
+        ));
       default:
         return null;
     }
@@ -64,7 +88,7 @@ const ShowResultView: React.FC<ShowResultViewProps> = ({
 
   return (
     <div className="result-box">
-      <h2>{t("progress.complete")}</h2>
+      <h2>{cancelled ? t("progress.cancelled") : t("progress.complete")}</h2>
       <h3 className="result-summary">
         {`${t("results.title")} : ${t("results.total", {
           count: totalFiles.length,
@@ -72,6 +96,9 @@ const ShowResultView: React.FC<ShowResultViewProps> = ({
           count: failedFiles.length,
         })} )`}
       </h3>
+      {rolledBack && (
+        <p className="result-rollback">{t("results.rolledBack", { reason: rolledBack.reason })}</p>
+      )}
 
       <div className="result-tabs">
         <button
@@ -92,9 +119,27 @@ const ShowResultView: React.FC<ShowResultViewProps> = ({
         >
           {t("results.failed", { count: failedFiles.length })}
         </button>
+        {(cancelled || rolledBack) && (
+          <button
+            className={`tab ${activeTab === "notStarted" ? "active" : ""}`}
+            onClick={() => setActiveTab("notStarted")}
+          >
+            {t("results.notStarted", { count: notStarted.length })}
+          </button>
+        )}
       </div>
       <ul className="result-list">{renderFileList()}</ul>
 
+      {cancelled && cancelled.outputCount > 0 && (
+        <button className="button-secondary" onClick={onRemoveOutputs}>
+          {t("results.removeOutputs", { count: cancelled.outputCount })}
+        </button>
+      )}
+      {onExportReport && (
+        <button className="button-secondary" onClick={onExportReport}>
+          {t("results.exportReport")}
+        </button>
+      )}
       <button className="button-primary" onClick={onClose}>
         {t("results.close")}
       </button>
@@ -32,6 +32,21 @@ const Sidebar: React.FC<SidebarProps> = ({ activePage, onNavigate }) => {
               {t("delete.title")}
             </a>
           </li>
+          <li className={activePage === "mirror" ? "active" : ""}>
+            <a href="#" onClick={() => onNavigate("mirror")}>
+              {t("mirror.title")}
+            </a>
+          </li>
+          <li className={activePage === "keys" ? "active" : ""}>
+            <a href="#" onClick={() => onNavigate("keys")}>
+              {t("keys.title")}
+            </a>
+          </li>
+          <li className={activePage === "audit" ? "active" : ""}>
+            <a href="#" onClick={() => onNavigate("audit")}>
+              {t("audit.title")}
+            </a>
+          </li>
           <li className={activePage === "settings" ? "active" : ""}>
             <a href="#" onClick={() => onNavigate("settings")}>
               {t("settings.title")}
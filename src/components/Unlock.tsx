@@ -1,25 +1,64 @@
-import React, { useState } from "react";
+import React, { useEffect, useState } from "react";
 import { useTranslation } from "react-i18next";
+import { invoke } from "@tauri-apps/api/core";
 import LanguageSwitcher from "./LanguageSwitcher";
+import VaultSwitcher from "./VaultSwitcher";
 import "./Unlock.css";
 
 interface UnlockProps {
   onUnlock: (password: string) => Promise<void>;
+  onVaultChanged: () => void;
+  onCreateNew: (path: string) => void;
+  onRestore: (source: string) => void;
 }
 
-const Unlock: React.FC<UnlockProps> = ({ onUnlock }) => {
+// 연속 실패 횟수와 다음 시도까지 남은 시간 (백엔드 throttle::UnlockStatus)
+interface UnlockStatus {
+  failedAttempts: number;
+  retryAfterSeconds: number;
+  attemptsBeforeWipe: number | null;
+  tampered: boolean;
+}
+
+const Unlock: React.FC<UnlockProps> = ({ onUnlock, onVaultChanged, onCreateNew, onRestore }) => {
   const { t } = useTranslation();
   const [password, setPassword] = useState("");
   const [errorKey, setErrorKey] = useState<string | null>(null);
+  const [status, setStatus] = useState<UnlockStatus | null>(null);
+  const [retryAfter, setRetryAfter] = useState(0);
+
+  const loadStatus = async () => {
+    try {
+      const next = await invoke<UnlockStatus>("get_unlock_status");
+      setStatus(next);
+      setRetryAfter(next.retryAfterSeconds);
+    } catch (e) {
+      console.error(String(e));
+    }
+  };
+
+  useEffect(() => {
+    loadStatus();
+  }, []);
+
+  // 대기 시간이 남아 있으면 1초마다 줄여서 표시
+  useEffect(() => {
+    if (retryAfter <= 0) return;
+    const timer = setTimeout(() => setRetryAfter((prev) => prev - 1), 1000);
+    return () => clearTimeout(timer);
+  }, [retryAfter]);
 
   const handleUnlockClick = async () => {
+    if (retryAfter > 0) return;
     setErrorKey(""); // 이전 에러 메시지 초기화
 
     try {
       await onUnlock(password);
     } catch (e) {
       console.error(String(e));
-      setErrorKey("error.operationFailed");
+      setErrorKey("error.unlockFailed");
+      setPassword("");
+      await loadStatus();
     }
   };
 
@@ -28,6 +67,7 @@ const Unlock: React.FC<UnlockProps> = ({ onUnlock }) => {
       <LanguageSwitcher isAbsolute={true} />
       <div className="unlock-box">
         <h1>SecureVault</h1>
+        <VaultSwitcher onChanged={onVaultChanged} onCreateNew={onCreateNew} onRestore={onRestore} />
         <p>{t("unlock.prompt")}</p>
         <input
           type="password"
@@ -38,7 +78,12 @@ const Unlock: React.FC<UnlockProps> = ({ onUnlock }) => {
           onKeyDown={(e) => e.key === "Enter" && handleUnlockClick()}
         />
         {errorKey && <p className="error-message">{t(errorKey)}</p>}
-        <button className="unlock-button" onClick={handleUnlockClick}>
+        {status?.tampered && <p className="error-message">{t("unlock.tampered")}</p>}
+        {retryAfter > 0 && <p className="error-message">{t("unlock.retryAfter", { count: retryAfter })}</p>}
+        {status && status.failedAttempts > 0 && status.attemptsBeforeWipe != null && (
+          <p className="error-message">{t("unlock.attemptsBeforeWipe", { count: status.attemptsBeforeWipe })}</p>
+        )}
+        <button className="unlock-button" onClick={handleUnlockClick} disabled={retryAfter > 0}>
           {t("unlock.button")}
         </button>
       </div>
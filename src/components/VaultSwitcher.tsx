@@ -0,0 +1,114 @@
+import React, { useEffect, useState } from "react";
+import { useTranslation } from "react-i18next";
+import { invoke } from "@tauri-apps/api/core";
+import { open, save, ask, message } from "@tauri-apps/plugin-dialog";
+import "./VaultSwitcher.css";
+
+// 등록된 vault 하나 (백엔드 vaults::VaultInfo)
+interface VaultInfo {
+  id: string;
+  name: string;
+  path: string;
+  exists: boolean;
+  selected: boolean;
+}
+
+interface VaultSwitcherProps {
+  onChanged: () => void; // 선택된 vault 가 바뀌었을 때 (잠금 화면/설정 화면을 다시 결정)
+  onCreateNew: (path: string) => void; // 새 vault 를 만들 위치를 고른 경우
+  onRestore: (source: string) => void; // 내보낸 헤더나 자동 백업 파일을 고른 경우
+}
+
+const VaultSwitcher: React.FC<VaultSwitcherProps> = ({ onChanged, onCreateNew, onRestore }) => {
+  const { t } = useTranslation();
+  const [vaults, setVaults] = useState<VaultInfo[]>([]);
+
+  const loadVaults = async () => {
+    try {
+      setVaults(await invoke<VaultInfo[]>("list_vaults"));
+    } catch (e) {
+      console.error(String(e));
+    }
+  };
+
+  useEffect(() => {
+    loadVaults();
+  }, []);
+
+  const selected = vaults.find((vault) => vault.selected);
+
+  const runAndReload = async (command: string, args: Record<string, unknown>) => {
+    try {
+      await invoke(command, args);
+      await loadVaults();
+      onChanged();
+    } catch (e) {
+      console.error(String(e));
+      await message(String(e));
+    }
+  };
+
+  const handleSwitch = (id: string) => runAndReload("switch_vault", { id });
+
+  // USB 드라이브 등 다른 위치에 있는 기존 vault 파일 열기
+  const handleOpen = async () => {
+    const path = await open({ multiple: false, title: t("vaults.openTitle") });
+    if (typeof path === "string") {
+      await runAndReload("open_vault", { path });
+    }
+  };
+
+  const handleCreate = async () => {
+    const path = await save({ title: t("vaults.createTitle"), defaultPath: "vault.key" });
+    if (path) {
+      onCreateNew(path);
+    }
+  };
+
+  // 선택된 vault 위치에 헤더를 복원 (vault 파일이 손상되었거나 새로 설치한 경우)
+  const handleRestore = async () => {
+    const source = await open({ multiple: false, title: t("vaults.restoreTitle") });
+    if (typeof source === "string") {
+      onRestore(source);
+    }
+  };
+
+  // 목록에서만 지우고 파일은 그대로 둠
+  const handleForget = async () => {
+    if (!selected) return;
+    const confirmed = await ask(t("vaults.forgetConfirm", { name: selected.name, path: selected.path }), {
+      kind: "warning",
+    });
+    if (confirmed) {
+      await runAndReload("forget_vault", { id: selected.id });
+    }
+  };
+
+  return (
+    <div className="vault-switcher">
+      <select
+        className="vault-select"
+        value={selected?.id ?? ""}
+        onChange={(e) => handleSwitch(e.target.value)}
+        title={selected?.path}
+      >
+        {!selected && <option value="">{t("vaults.none")}</option>}
+        {vaults.map((vault) => (
+          <option key={vault.id} value={vault.id}>
+            {vault.exists ? vault.name : `${vault.name} (${t("vaults.missing")})`}
+          </option>
+        ))}
+      </select>
+      <div className="vault-actions">
+        <button onClick={handleOpen}>{t("vaults.open")}</button>
+        <button onClick={handleCreate}>{t("vaults.create")}</button>
+        <button onClick={handleRestore}>{t("vaults.restore")}</button>
+        <button onClick={handleForget} disabled={!selected}>
+          {t("vaults.forget")}
+        </button>
+      </div>
+    </div>
+  );
+};
+
+export default VaultSwitcher;
@@ -1,4 +1,17 @@
-export type Page = "encrypt" | "decrypt" | "delete" | "settings";
+export type Page = "encrypt" | "decrypt" | "delete" | "mirror" | "audit" | "keys" | "settings";
+// 본문 암호화 알고리즘 (백엔드 cipher::Cipher)
+export type Cipher = "AES-256-GCM" | "XCHACHA20-POLY1305" | "AES-256-GCM-SIV";
+export const CIPHERS: Cipher[] = ["AES-256-GCM", "XCHACHA20-POLY1305", "AES-256-GCM-SIV"];
+// 암호화 형식과 누가 열 수 있는지 (백엔드 encrypt_files 의 format/recipients/password/cipher)
+// VAULT: 이 vault 키 (SecureVault 형식만), RECIPIENTS: 수신자 공개키, PASSWORD: vault 와 상관없는 일회용 비밀번호
+export type EncryptOptions = {
+  format: "SECURE_VAULT" | "AGE";
+  mode: "VAULT" | "RECIPIENTS" | "PASSWORD";
+  recipients: string[];
+  password: string;
+  confirm: string;
+  cipher: Cipher | null; // null 이면 설정의 기본 알고리즘
+};
 export type StagedFile = {
   path: string;
 };